@@ -0,0 +1,182 @@
+use ecs::{
+    Entity, EntityPool, EntityProvider, Layer, PhysicsComponent, PositionComponent,
+    VisualComponent, ECS,
+};
+use glam::Vec3;
+use std::{
+    cmp::Ordering,
+    env,
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+const ENTITY_COUNTS: [usize; 3] = [10_000, 50_000, 100_000];
+const ITERATIONS: u32 = 100;
+// short-lived entities per pass, like projectiles of a busy wave
+const CHURN_COUNT: usize = 1_000;
+
+struct Wave {
+    entities: Vec<Entity>,
+}
+
+impl EntityProvider for Wave {
+    fn get_entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    fn get_player(&self) -> Option<Entity> {
+        None
+    }
+}
+
+fn spawn_wave(ecs: &mut ECS<()>, count: usize) -> Wave {
+    let mut entities = Vec::with_capacity(count);
+
+    for index in 0..count {
+        let entity = ecs.create_entity();
+        let offset = index as f32 / count as f32;
+
+        ecs.add_component(
+            entity,
+            PositionComponent {
+                xyz: Vec3::new(offset, -offset, offset),
+                scale: Vec3::new(0.1, 0.1, 1.0),
+            },
+        );
+        ecs.add_component(
+            entity,
+            VisualComponent::new(vec![0, 1, 2, 3], Layer::Game, 0.2),
+        );
+        // only every other entity moves, joins have to skip the rest
+        if index % 2 == 0 {
+            ecs.add_component(
+                entity,
+                PhysicsComponent {
+                    velocity: Vec3::new(0.06, 0.0, 0.0),
+                },
+            );
+        }
+
+        entities.push(entity);
+    }
+
+    // churn the storages like a running wave does
+    for entity in entities.iter().step_by(10) {
+        ecs.destroy_entity(*entity);
+    }
+    entities.retain(|entity| ecs.is_alive(*entity));
+
+    Wave { entities }
+}
+
+fn spawn_projectile(ecs: &mut ECS<()>) -> Entity {
+    let projectile = ecs.create_entity();
+
+    ecs.add_component(
+        projectile,
+        PositionComponent {
+            xyz: Vec3::ZERO,
+            scale: Vec3::new(0.05, 0.05, 1.0),
+        },
+    );
+    ecs.add_component(projectile, VisualComponent::new(vec![0], Layer::Game, 0.0));
+    ecs.add_component(
+        projectile,
+        PhysicsComponent {
+            velocity: Vec3::new(0.6, 0.0, 0.0),
+        },
+    );
+
+    projectile
+}
+
+fn bench<F>(name: &str, count: usize, mut pass: F)
+where
+    F: FnMut(),
+{
+    // warm up caches and allocations
+    pass();
+
+    let mut total = Duration::ZERO;
+    let mut slowest = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        pass();
+        let elapsed = start.elapsed();
+
+        total += elapsed;
+        slowest = slowest.max(elapsed);
+    }
+
+    println!(
+        "{name:<24} {count:>7} entities: avg {:>10.3?} max {:>10.3?}",
+        total / ITERATIONS,
+        slowest
+    );
+}
+
+// mirrors what the render system does before handing the frame to the renderer
+fn render_prep(ecs: &mut ECS<()>, wave: &Wave) -> usize {
+    let mut targets: Vec<(u8, f32, usize)> = ecs
+        .query_in::<(&mut VisualComponent, &PositionComponent), Wave>(wave)
+        .filter(|(_, (visual, _))| visual.should_render())
+        .map(|(_, (mut visual, position))| {
+            visual.update_animation(1.0 / 60.0);
+            (
+                visual.layer.value(),
+                position.xyz.z,
+                visual.get_current_texture(),
+            )
+        })
+        .collect();
+
+    targets.sort_by(|a, b| match a.0.cmp(&b.0) {
+        Ordering::Equal => a.1.total_cmp(&b.1),
+        ordering => ordering,
+    });
+
+    targets.len()
+}
+
+fn main() {
+    // the texture table uses paths relative to the workspace root
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    env::set_current_dir(workspace_root).expect("Failed to enter workspace root!");
+
+    for count in ENTITY_COUNTS {
+        let mut ecs: ECS<()> =
+            ECS::create("res/texture_table.json", "res/fonts").expect("Failed to create the ecs!");
+        let wave = spawn_wave(&mut ecs, count);
+
+        bench("physics", count, || ecs.step(&wave));
+
+        bench("render prep", count, || {
+            black_box(render_prep(&mut ecs, &wave));
+        });
+
+        bench("render prep (all)", count, || {
+            let visible = ecs
+                .query::<(&VisualComponent, &PositionComponent)>()
+                .filter(|(_, (visual, _))| visual.should_render())
+                .count();
+            black_box(visible);
+        });
+
+        let mut projectiles = Vec::with_capacity(CHURN_COUNT);
+        bench("churn (destroy)", count, || {
+            projectiles.extend((0..CHURN_COUNT).map(|_| spawn_projectile(&mut ecs)));
+            for projectile in projectiles.drain(..) {
+                ecs.destroy_entity(projectile);
+            }
+        });
+
+        let mut pool = EntityPool::default();
+        bench("churn (pool)", count, || {
+            projectiles.extend((0..CHURN_COUNT).map(|_| pool.acquire(&mut ecs, spawn_projectile)));
+            for projectile in projectiles.drain(..) {
+                pool.release(&mut ecs, projectile);
+            }
+        });
+    }
+}
@@ -0,0 +1,154 @@
+use super::{
+    component::Component,
+    entity::{Entity, EntityManager},
+    ECS,
+};
+
+use std::{cell::RefCell, mem};
+
+type Command<E> = Box<dyn FnOnce(&mut ECS<E>)>;
+
+pub(crate) struct CommandQueue<E>
+where
+    E: 'static,
+{
+    commands: RefCell<Vec<Command<E>>>,
+}
+
+impl<E> Default for CommandQueue<E>
+where
+    E: 'static,
+{
+    fn default() -> Self {
+        Self {
+            commands: RefCell::new(Vec::with_capacity(10)),
+        }
+    }
+}
+
+impl<E> CommandQueue<E>
+where
+    E: 'static,
+{
+    pub fn take(&self) -> Vec<Command<E>> {
+        mem::take(&mut *self.commands.borrow_mut())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.borrow().is_empty()
+    }
+}
+
+// records changes to the world that are applied at the next sync point
+pub struct Commands<'ecs, E>
+where
+    E: 'static,
+{
+    queue: &'ecs CommandQueue<E>,
+    entity_manager: &'ecs EntityManager,
+}
+
+impl<'ecs, E> Commands<'ecs, E>
+where
+    E: 'static,
+{
+    pub(crate) fn new(queue: &'ecs CommandQueue<E>, entity_manager: &'ecs EntityManager) -> Self {
+        Self {
+            queue,
+            entity_manager,
+        }
+    }
+
+    // the entity is reserved right away, but only alive once the commands were applied
+    #[must_use]
+    pub fn spawn(&self) -> Entity {
+        self.entity_manager.reserve_entity()
+    }
+
+    pub fn insert<T>(&self, entity: Entity, component: T)
+    where
+        T: Component,
+    {
+        self.add(move |ecs| ecs.add_component(entity, component));
+    }
+
+    pub fn remove<T>(&self, entity: Entity)
+    where
+        T: Component,
+    {
+        self.add(move |ecs| {
+            ecs.remove_component::<T>(entity);
+        });
+    }
+
+    pub fn despawn(&self, entity: Entity) {
+        self.add(move |ecs| ecs.destroy_entity(entity));
+    }
+
+    pub fn send_event<T>(&self, event: T)
+    where
+        T: 'static,
+    {
+        self.add(move |ecs| ecs.send_event(event));
+    }
+
+    // anything else, e.g. wiring up input links of spawned entities
+    pub fn add<F>(&self, command: F)
+    where
+        F: FnOnce(&mut ECS<E>) + 'static,
+    {
+        self.queue.commands.borrow_mut().push(Box::new(command));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::{self, Health};
+
+    #[test]
+    fn spawned_entities_get_their_components() {
+        let mut ecs = testing::ecs();
+
+        let entity = {
+            let commands = ecs.commands();
+            let entity = commands.spawn();
+            commands.insert(entity, Health(10));
+            entity
+        };
+        assert!(!ecs.is_alive(entity));
+
+        ecs.apply_commands();
+
+        assert!(ecs.is_alive(entity));
+        assert_eq!(ecs.get_component::<Health>(entity), Some(&Health(10)));
+    }
+
+    #[test]
+    fn nested_commands_can_spawn_and_insert() {
+        let mut ecs = testing::ecs();
+
+        ecs.commands().add(|ecs| {
+            let commands = ecs.commands();
+            let entity = commands.spawn();
+            commands.insert(entity, Health(5));
+            commands.add(move |ecs| ecs.add_component(entity, Health(7)));
+        });
+        ecs.apply_commands();
+
+        let entities: Vec<_> = ecs.entities().collect();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(ecs.get_component::<Health>(entities[0]), Some(&Health(7)));
+    }
+
+    #[test]
+    fn despawn_is_deferred() {
+        let mut ecs = testing::ecs();
+        let entity = ecs.create_entity();
+
+        ecs.commands().despawn(entity);
+        assert!(ecs.is_alive(entity));
+
+        ecs.apply_commands();
+        assert!(!ecs.is_alive(entity));
+    }
+}
@@ -1,18 +1,19 @@
-use super::{Layer, PositionComponent, TextComponent, VisualComponent};
+use super::{Layer, TextComponent, TransformComponent, VisualComponent};
 
-pub struct TextWithPosition<'component> {
+pub struct TextWithTransform<'component> {
     pub text: &'component mut TextComponent,
-    pub position: &'component PositionComponent,
+    pub changed: bool,
+    pub transform: &'component TransformComponent,
 }
 
-pub struct VisualWithPosition<'component> {
+pub struct VisualWithTransform<'component> {
     pub visual: &'component VisualComponent,
-    pub position: &'component PositionComponent,
+    pub transform: &'component TransformComponent,
 }
 
 pub enum RenderTarget<'component> {
-    Visual(VisualWithPosition<'component>),
-    Text(TextWithPosition<'component>),
+    Visual(VisualWithTransform<'component>),
+    Text(TextWithTransform<'component>),
 }
 
 impl<'component> RenderTarget<'component> {
@@ -23,10 +24,10 @@ impl<'component> RenderTarget<'component> {
         }
     }
 
-    pub fn get_position(&self) -> &PositionComponent {
+    pub fn get_transform(&self) -> &TransformComponent {
         match self {
-            RenderTarget::Visual(v) => v.position,
-            RenderTarget::Text(t) => t.position,
+            RenderTarget::Visual(v) => v.transform,
+            RenderTarget::Text(t) => t.transform,
         }
     }
 }
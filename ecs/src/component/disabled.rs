@@ -0,0 +1,15 @@
+use super::{
+    super::inspect::{FieldValue, Inspect, InspectContext},
+    Component,
+};
+
+// marks entities that wait in a pool, queries skip them, see ECS::disable_entity
+pub(crate) struct DisabledComponent;
+
+impl Component for DisabledComponent {}
+
+impl Inspect for DisabledComponent {
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        vec![("disabled", FieldValue::text(true))]
+    }
+}
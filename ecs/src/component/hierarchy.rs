@@ -0,0 +1,63 @@
+use super::{
+    super::{
+        entity::Entity,
+        inspect::{FieldValue, Inspect, InspectContext},
+        snapshot::{LoadContext, SaveContext, SavedComponent, SnapshotError},
+    },
+    Component,
+};
+
+// managed through ECS::set_parent to keep both sides of the relation in sync
+pub struct ParentComponent {
+    pub(crate) parent: Entity,
+}
+
+impl Component for ParentComponent {}
+
+// children are restored from their parents when loading
+impl SavedComponent for ParentComponent {
+    type Saved = usize;
+
+    fn save(&self, context: &SaveContext) -> Option<Self::Saved> {
+        context.entity_id(self.parent)
+    }
+
+    fn load(saved: Self::Saved, context: &LoadContext) -> Result<Self, SnapshotError> {
+        Ok(Self {
+            parent: context.entity(saved)?,
+        })
+    }
+}
+
+impl Inspect for ParentComponent {
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        vec![("parent", FieldValue::text(self.parent))]
+    }
+}
+
+impl ParentComponent {
+    #[must_use]
+    pub fn get(&self) -> Entity {
+        self.parent
+    }
+}
+
+#[derive(Default)]
+pub struct ChildrenComponent {
+    pub(crate) children: Vec<Entity>,
+}
+
+impl Component for ChildrenComponent {}
+
+impl Inspect for ChildrenComponent {
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        vec![("children", FieldValue::entities(&self.children))]
+    }
+}
+
+impl ChildrenComponent {
+    #[must_use]
+    pub fn get(&self) -> &[Entity] {
+        &self.children
+    }
+}
@@ -1,4 +1,7 @@
-use super::{ComponentStorage, Entity};
+use super::{
+    super::inspect::{FieldValue, Inspect, InspectContext},
+    Component, ComponentStorage, Entity,
+};
 
 use winit::event_loop::EventLoopProxy;
 
@@ -12,22 +15,27 @@ where
     pub previous: Option<Entity>,
 }
 
-impl<E> ComponentStorage<InputComponent<E>>
+impl<E> Component for InputComponent<E> where E: 'static {}
+
+impl<E> Inspect for InputComponent<E>
 where
     E: 'static,
 {
-    pub fn get_active_entity(&self) -> Option<&Entity> {
-        for (entity, input) in &self.components {
-            if input.is_active {
-                return Some(entity);
-            }
-        }
-
-        None
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        vec![
+            ("active", FieldValue::text(self.is_active)),
+            ("next", FieldValue::optional_entity(self.next)),
+            ("previous", FieldValue::optional_entity(self.previous)),
+        ]
     }
+}
 
+impl<E> ComponentStorage<InputComponent<E>>
+where
+    E: 'static,
+{
     pub fn set_next_of(&mut self, current: Entity, next: Entity) {
-        let Some(current_input) = self.components.get_mut(&current) else {
+        let Some(current_input) = self.get_mut(current) else {
             return;
         };
 
@@ -35,7 +43,7 @@ where
     }
 
     pub fn set_previous_of(&mut self, current: Entity, previous: Entity) {
-        let Some(current_input) = self.components.get_mut(&current) else {
+        let Some(current_input) = self.get_mut(current) else {
             return;
         };
 
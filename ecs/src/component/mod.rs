@@ -1,100 +1,346 @@
+mod disabled;
+mod hierarchy;
 mod input;
+mod name;
 mod physics;
 mod position;
 mod text;
 mod visual;
 
 use super::entity::Entity;
-use std::collections::HashMap;
+use rendering::ImageData;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
 
 pub mod composition;
 
+pub(crate) use disabled::DisabledComponent;
+pub use hierarchy::{ChildrenComponent, ParentComponent};
 pub use input::InputComponent;
+pub(crate) use name::NameIndex;
+pub use name::{NameComponent, TagComponent};
 pub use physics::PhysicsComponent;
-pub use position::{PositionComponent, Quad};
+pub(crate) use position::PreviousPosition;
+pub use position::{PositionComponent, Quad, TransformComponent};
 pub use text::{TextComponent, TextContent};
 pub use visual::{Layer, VisualComponent};
 
-pub enum Component<E>
-where
-    E: 'static,
-{
-    Position(PositionComponent),
-    Visual(VisualComponent),
-    Text(TextComponent),
-    Input(InputComponent<E>),
-    Physics(PhysicsComponent),
+pub trait Component: 'static {
+    // called when the component leaves its storage, gpu images are handed over instead of destroyed,
+    // frames in flight might still use them
+    fn release(&mut self, _images: &mut Vec<ImageData>) {}
 }
 
+#[derive(Clone, Copy)]
+pub struct ComponentTicks {
+    pub added: u64,
+    pub changed: u64,
+}
+
+// sparse set: components are packed densely, sparse maps an entity index to its dense slot
 pub struct ComponentStorage<T> {
-    components: HashMap<Entity, T>,
+    sparse: Vec<Option<usize>>,
+    entities: Vec<Entity>,
+    components: Vec<T>,
+    ticks: Vec<ComponentTicks>,
+    // set by the manager whenever the storage is borrowed mutably
+    change_tick: u64,
+    // images of removed components, destroyed by the renderer on the next frame
+    released: Vec<ImageData>,
 }
 
-impl<T> ComponentStorage<T> {
+impl<T> ComponentStorage<T>
+where
+    T: Component,
+{
     fn new() -> Self {
         Self {
-            components: HashMap::new(),
+            sparse: Vec::new(),
+            entities: Vec::new(),
+            components: Vec::new(),
+            ticks: Vec::new(),
+            change_tick: 0,
+            released: Vec::new(),
         }
     }
 
+    fn dense_index(&self, entity: Entity) -> Option<usize> {
+        let dense = (*self.sparse.get(entity.index() as usize)?)?;
+
+        // the slot may belong to an older generation of this index
+        (self.entities[dense] == entity).then_some(dense)
+    }
+
     pub fn add(&mut self, entity: Entity, component: T) {
-        self.components.insert(entity, component);
+        let index = entity.index() as usize;
+        if index >= self.sparse.len() {
+            self.sparse.resize(index + 1, None);
+        }
+
+        if let Some(dense) = self.sparse[index] {
+            // a component of an older generation counts as newly added
+            if self.entities[dense] != entity {
+                self.ticks[dense].added = self.change_tick;
+            }
+            self.ticks[dense].changed = self.change_tick;
+
+            self.entities[dense] = entity;
+            let mut replaced = std::mem::replace(&mut self.components[dense], component);
+            replaced.release(&mut self.released);
+            return;
+        }
+
+        self.sparse[index] = Some(self.components.len());
+        self.entities.push(entity);
+        self.components.push(component);
+        self.ticks.push(ComponentTicks {
+            added: self.change_tick,
+            changed: self.change_tick,
+        });
     }
 
-    fn remove(&mut self, entity: Entity) {
-        self.components.remove(&entity);
+    // the returned component no longer owns any gpu images
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        let dense = self.dense_index(entity)?;
+
+        self.sparse[entity.index() as usize] = None;
+        self.entities.swap_remove(dense);
+        self.ticks.swap_remove(dense);
+        let mut component = self.components.swap_remove(dense);
+
+        // the last element was moved into the freed slot
+        if let Some(moved) = self.entities.get(dense) {
+            self.sparse[moved.index() as usize] = Some(dense);
+        }
+
+        component.release(&mut self.released);
+        Some(component)
     }
 
     pub fn get(&self, entity: Entity) -> Option<&T> {
-        self.components.get(&entity)
+        let dense = self.dense_index(entity)?;
+        Some(&self.components[dense])
     }
 
+    // mutable access counts as a change, whether or not anything is written
     pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
-        self.components.get_mut(&entity)
+        let dense = self.dense_index(entity)?;
+        self.ticks[dense].changed = self.change_tick;
+        Some(&mut self.components[dense])
+    }
+
+    pub(crate) fn get_mut_with_ticks(
+        &mut self,
+        entity: Entity,
+    ) -> Option<(&mut T, &mut ComponentTicks)> {
+        let dense = self.dense_index(entity)?;
+        Some((&mut self.components[dense], &mut self.ticks[dense]))
+    }
+
+    pub fn get_ticks(&self, entity: Entity) -> Option<ComponentTicks> {
+        let dense = self.dense_index(entity)?;
+        Some(self.ticks[dense])
+    }
+
+    pub fn change_tick(&self) -> u64 {
+        self.change_tick
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
-        self.components
-            .iter_mut()
-            .map(|(entity, component)| (*entity, component))
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
     }
 }
 
-pub(crate) struct ComponentManager<E>
-where
-    E: 'static,
-{
-    pub visual_storage: ComponentStorage<VisualComponent>,
-    pub position_storage: ComponentStorage<PositionComponent>,
-    pub input_storage: ComponentStorage<InputComponent<E>>,
-    pub text_storage: ComponentStorage<TextComponent>,
-    pub physics_storage: ComponentStorage<PhysicsComponent>,
+// type erased access so the manager can hold storages of any component type
+trait AnyStorage {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn destroy_entity(&mut self, entity: Entity);
+    fn take_released(&mut self, images: &mut Vec<ImageData>);
+    fn release_all(&mut self, images: &mut Vec<ImageData>);
 }
 
-impl<E> ComponentManager<E>
+impl<T> AnyStorage for ComponentStorage<T>
 where
-    E: 'static,
+    T: Component,
 {
-    pub fn create() -> Self {
-        Self {
-            visual_storage: ComponentStorage::new(),
-            position_storage: ComponentStorage::new(),
-            input_storage: ComponentStorage::new(),
-            text_storage: ComponentStorage::new(),
-            physics_storage: ComponentStorage::new(),
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn destroy_entity(&mut self, entity: Entity) {
+        self.remove(entity);
+    }
+
+    fn take_released(&mut self, images: &mut Vec<ImageData>) {
+        images.append(&mut self.released);
+    }
+
+    fn release_all(&mut self, images: &mut Vec<ImageData>) {
+        images.append(&mut self.released);
+        for component in &mut self.components {
+            component.release(images);
         }
     }
+}
+
+pub struct ComponentManager {
+    storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+    // advances with every system run, stamped on added and changed components
+    change_tick: u64,
+    // change tick of the previous run of whatever is running now, see begin_run
+    last_run: u64,
+}
+
+impl ComponentManager {
+    pub fn create<E>() -> Self
+    where
+        E: 'static,
+    {
+        let mut component_manager = Self {
+            storages: HashMap::with_capacity(10),
+            change_tick: 1,
+            last_run: 0,
+        };
+
+        // built-in storages always exist, systems rely on them
+        component_manager.register::<VisualComponent>();
+        component_manager.register::<PositionComponent>();
+        component_manager.register::<InputComponent<E>>();
+        component_manager.register::<TextComponent>();
+        component_manager.register::<PhysicsComponent>();
+        component_manager.register::<ParentComponent>();
+        component_manager.register::<ChildrenComponent>();
+        component_manager.register::<TransformComponent>();
+        component_manager.register::<DisabledComponent>();
+        component_manager.register::<PreviousPosition>();
+
+        component_manager
+    }
+
+    // returns the tick of this run, changes made before it are visible to is_changed
+    pub fn begin_run(&mut self, last_run: u64) -> u64 {
+        self.last_run = last_run;
+        self.change_tick += 1;
+        self.change_tick
+    }
+
+    pub fn end_run(&mut self) {
+        // anything changed after the run has to be newer than the run itself
+        self.change_tick += 1;
+        self.last_run = 0;
+    }
+
+    pub fn last_run(&self) -> u64 {
+        self.last_run
+    }
+
+    pub fn register<T>(&mut self) -> &mut ComponentStorage<T>
+    where
+        T: Component,
+    {
+        let storage: &mut ComponentStorage<T> = self
+            .storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(ComponentStorage::<T>::new()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("Storage registered under the wrong type!");
+
+        storage.change_tick = self.change_tick;
+        storage
+    }
+
+    pub fn storage<T>(&self) -> Option<&ComponentStorage<T>>
+    where
+        T: Component,
+    {
+        self.storages
+            .get(&TypeId::of::<T>())
+            .and_then(|storage| storage.as_any().downcast_ref())
+    }
+
+    pub fn storage_mut<T>(&mut self) -> Option<&mut ComponentStorage<T>>
+    where
+        T: Component,
+    {
+        let storage: &mut ComponentStorage<T> = self
+            .storages
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|storage| storage.as_any_mut().downcast_mut())?;
+
+        storage.change_tick = self.change_tick;
+        Some(storage)
+    }
 
     pub fn clear_entity(&mut self, entity: Entity) {
-        self.visual_storage.remove(entity);
-        self.position_storage.remove(entity);
-        self.input_storage.remove(entity);
-        self.physics_storage.remove(entity);
+        for storage in self.storages.values_mut() {
+            storage.destroy_entity(entity);
+        }
+    }
+
+    // images of components removed since the last call, see Renderer::destroy_image_data
+    pub fn take_released(&mut self) -> Vec<ImageData> {
+        let mut images = Vec::new();
+        for storage in self.storages.values_mut() {
+            storage.take_released(&mut images);
+        }
 
-        self.text_storage.destroy_entity(entity);
+        images
     }
 
+    // the device has to be idle, the images are destroyed right away
     pub fn destroy(&mut self) {
-        self.text_storage.destroy();
+        let mut images = Vec::new();
+        for storage in self.storages.values_mut() {
+            storage.release_all(&mut images);
+        }
+
+        for image_data in images {
+            image_data.destroy();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::testing, Component, ComponentStorage};
+
+    use rendering::ImageData;
+    use std::{cell::Cell, rc::Rc};
+
+    struct Counted(Rc<Cell<u32>>);
+
+    impl Component for Counted {
+        fn release(&mut self, _images: &mut Vec<ImageData>) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn replaced_and_removed_components_are_released_once() {
+        let mut ecs = testing::ecs();
+        let entity = ecs.create_entity();
+        let releases = Rc::new(Cell::new(0));
+
+        let mut storage = ComponentStorage::new();
+        storage.add(entity, Counted(releases.clone()));
+        storage.add(entity, Counted(releases.clone()));
+        assert_eq!(releases.get(), 1);
+
+        assert!(storage.remove(entity).is_some());
+        assert_eq!(releases.get(), 2);
+        assert!(storage.remove(entity).is_none());
+        assert_eq!(releases.get(), 2);
     }
 }
@@ -0,0 +1,224 @@
+use super::{
+    super::{
+        entity::Entity,
+        inspect::{FieldValue, Inspect, InspectContext},
+        snapshot::{LoadContext, SaveContext, SavedComponent, SnapshotError},
+    },
+    Component,
+};
+
+use std::collections::HashMap;
+
+// set through ECS::set_name, which keeps the name index up to date
+pub struct NameComponent {
+    name: String,
+}
+
+impl Component for NameComponent {}
+
+impl SavedComponent for NameComponent {
+    type Saved = String;
+
+    fn save(&self, _context: &SaveContext) -> Option<Self::Saved> {
+        Some(self.name.clone())
+    }
+
+    fn load(saved: Self::Saved, _context: &LoadContext) -> Result<Self, SnapshotError> {
+        Ok(Self { name: saved })
+    }
+}
+
+impl NameComponent {
+    #[must_use]
+    pub fn get(&self) -> &str {
+        &self.name
+    }
+}
+
+// set through ECS::add_tag / ECS::remove_tag
+#[derive(Default)]
+pub struct TagComponent {
+    tags: Vec<String>,
+}
+
+impl Component for TagComponent {}
+
+impl SavedComponent for TagComponent {
+    type Saved = Vec<String>;
+
+    fn save(&self, _context: &SaveContext) -> Option<Self::Saved> {
+        Some(self.tags.clone())
+    }
+
+    fn load(saved: Self::Saved, _context: &LoadContext) -> Result<Self, SnapshotError> {
+        Ok(Self { tags: saved })
+    }
+}
+
+impl Inspect for TagComponent {
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        vec![("tags", FieldValue::Text(self.tags.join(", ")))]
+    }
+}
+
+impl TagComponent {
+    #[must_use]
+    pub fn get(&self) -> &[String] {
+        &self.tags
+    }
+
+    #[must_use]
+    pub fn has(&self, tag: &str) -> bool {
+        self.tags.iter().any(|existing| existing == tag)
+    }
+}
+
+// lookup tables, entries are verified against the components when read
+#[derive(Default)]
+pub(crate) struct NameIndex {
+    names: HashMap<String, Vec<Entity>>,
+    tags: HashMap<String, Vec<Entity>>,
+}
+
+impl NameIndex {
+    pub fn named(&self, name: &str) -> &[Entity] {
+        self.names.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn tagged(&self, tag: &str) -> &[Entity] {
+        self.tags.get(tag).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn create_name(name: String) -> NameComponent {
+        NameComponent { name }
+    }
+
+    pub fn insert_name(&mut self, entity: Entity, name: &str) {
+        insert(&mut self.names, entity, name);
+    }
+
+    pub fn remove_name(&mut self, entity: Entity, name: &str) {
+        remove(&mut self.names, entity, name);
+    }
+
+    pub fn insert_tags(&mut self, entity: Entity, tags: &TagComponent) {
+        for tag in &tags.tags {
+            insert(&mut self.tags, entity, tag);
+        }
+    }
+
+    pub fn add_tag(&mut self, entity: Entity, tags: &mut TagComponent, tag: &str) {
+        if tags.has(tag) {
+            return;
+        }
+
+        tags.tags.push(tag.to_owned());
+        insert(&mut self.tags, entity, tag);
+    }
+
+    pub fn remove_tag(&mut self, entity: Entity, tags: &mut TagComponent, tag: &str) {
+        tags.tags.retain(|existing| existing != tag);
+        remove(&mut self.tags, entity, tag);
+    }
+
+    pub fn remove_entity(
+        &mut self,
+        entity: Entity,
+        name: Option<&NameComponent>,
+        tags: Option<&TagComponent>,
+    ) {
+        if let Some(name) = name {
+            remove(&mut self.names, entity, &name.name);
+        }
+
+        for tag in tags.map_or(&[][..], TagComponent::get) {
+            remove(&mut self.tags, entity, tag);
+        }
+    }
+}
+
+fn insert(index: &mut HashMap<String, Vec<Entity>>, entity: Entity, key: &str) {
+    let entities = index.entry(key.to_owned()).or_default();
+    if !entities.contains(&entity) {
+        entities.push(entity);
+    }
+}
+
+fn remove(index: &mut HashMap<String, Vec<Entity>>, entity: Entity, key: &str) {
+    let Some(entities) = index.get_mut(key) else {
+        return;
+    };
+
+    entities.retain(|existing| *existing != entity);
+    if entities.is_empty() {
+        index.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::testing;
+
+    #[test]
+    fn despawned_entities_leave_the_index() {
+        let mut ecs = testing::ecs();
+        let entity = ecs.create_entity();
+        ecs.set_name(entity, "player");
+        ecs.add_tag(entity, "friendly");
+
+        ecs.destroy_entity(entity);
+
+        assert_eq!(ecs.find_by_name("player"), None);
+        assert_eq!(ecs.entities_with_tag("friendly").count(), 0);
+        assert!(ecs.name_index.named("player").is_empty());
+        assert!(ecs.name_index.tagged("friendly").is_empty());
+
+        // a recycled slot does not inherit the old name or tags
+        let recycled = ecs.create_entity();
+        assert_eq!(ecs.get_name(recycled), None);
+        assert_eq!(ecs.find_by_name("player"), None);
+        assert_eq!(ecs.entities_with_tag("friendly").count(), 0);
+    }
+
+    #[test]
+    fn renamed_entities_are_found_by_their_new_name() {
+        let mut ecs = testing::ecs();
+        let first = ecs.create_entity();
+        let second = ecs.create_entity();
+        ecs.set_name(first, "enemy");
+        ecs.set_name(second, "enemy");
+
+        assert_eq!(ecs.find_by_name("enemy"), Some(first));
+
+        ecs.set_name(first, "boss");
+
+        assert_eq!(ecs.find_by_name("enemy"), Some(second));
+        assert_eq!(ecs.find_by_name("boss"), Some(first));
+        assert_eq!(ecs.name_index.named("enemy"), [second]);
+        assert_eq!(ecs.get_name(first), Some("boss"));
+    }
+
+    #[test]
+    fn removed_tags_leave_the_index() {
+        let mut ecs = testing::ecs();
+        let entity = ecs.create_entity();
+        ecs.add_tag(entity, "solid");
+        ecs.add_tag(entity, "solid");
+        ecs.add_tag(entity, "visible");
+
+        assert_eq!(ecs.entities_with_tag("solid").collect::<Vec<_>>(), [entity]);
+
+        ecs.remove_tag(entity, "solid");
+
+        assert_eq!(ecs.entities_with_tag("solid").count(), 0);
+        assert!(ecs.name_index.tagged("solid").is_empty());
+        assert_eq!(
+            ecs.entities_with_tag("visible").collect::<Vec<_>>(),
+            [entity]
+        );
+
+        // removing a tag the entity does not have changes nothing
+        ecs.remove_tag(entity, "solid");
+        assert_eq!(ecs.entities_with_tag("visible").count(), 1);
+    }
+}
@@ -1,9 +1,55 @@
+use super::{
+    super::{
+        inspect::{FieldValue, Inspect, InspectContext},
+        snapshot::{LoadContext, SaveContext, SavedComponent, SnapshotError},
+    },
+    Component,
+};
+
 use glam::Vec3;
 
 pub struct PhysicsComponent {
     pub velocity: Vec3,
 }
 
+impl Component for PhysicsComponent {}
+
+impl SavedComponent for PhysicsComponent {
+    type Saved = [f32; 3];
+
+    fn save(&self, _context: &SaveContext) -> Option<Self::Saved> {
+        Some(self.velocity.to_array())
+    }
+
+    fn load(saved: Self::Saved, _context: &LoadContext) -> Result<Self, SnapshotError> {
+        Ok(Self {
+            velocity: Vec3::from_array(saved),
+        })
+    }
+}
+
+impl Inspect for PhysicsComponent {
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        vec![
+            ("velocity_x", FieldValue::Number(self.velocity.x)),
+            ("velocity_y", FieldValue::Number(self.velocity.y)),
+            ("velocity_z", FieldValue::Number(self.velocity.z)),
+        ]
+    }
+
+    fn edit(&mut self, field: &str, value: f32) -> bool {
+        let target = match field {
+            "velocity_x" => &mut self.velocity.x,
+            "velocity_y" => &mut self.velocity.y,
+            "velocity_z" => &mut self.velocity.z,
+            _ => return false,
+        };
+
+        *target = value;
+        true
+    }
+}
+
 impl Default for PhysicsComponent {
     fn default() -> Self {
         Self {
@@ -1,10 +1,71 @@
+use super::{
+    super::{
+        inspect::{FieldValue, Inspect, InspectContext},
+        snapshot::{LoadContext, SaveContext, SavedComponent, SnapshotError},
+    },
+    Component,
+};
+
 use glam::{Mat4, Vec2, Vec3};
+use serde::{Deserialize, Serialize};
 
 pub struct PositionComponent {
     pub xyz: Vec3,
     pub scale: Vec3,
 }
 
+impl Component for PositionComponent {}
+
+#[derive(Deserialize, Serialize)]
+pub struct SavedPosition {
+    xyz: [f32; 3],
+    scale: [f32; 3],
+}
+
+impl SavedComponent for PositionComponent {
+    type Saved = SavedPosition;
+
+    fn save(&self, _context: &SaveContext) -> Option<Self::Saved> {
+        Some(SavedPosition {
+            xyz: self.xyz.to_array(),
+            scale: self.scale.to_array(),
+        })
+    }
+
+    fn load(saved: Self::Saved, _context: &LoadContext) -> Result<Self, SnapshotError> {
+        Ok(Self {
+            xyz: Vec3::from_array(saved.xyz),
+            scale: Vec3::from_array(saved.scale),
+        })
+    }
+}
+
+impl Inspect for PositionComponent {
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        vec![
+            ("x", FieldValue::Number(self.xyz.x)),
+            ("y", FieldValue::Number(self.xyz.y)),
+            ("z", FieldValue::Number(self.xyz.z)),
+            ("width", FieldValue::Number(self.scale.x)),
+            ("height", FieldValue::Number(self.scale.y)),
+        ]
+    }
+
+    fn edit(&mut self, field: &str, value: f32) -> bool {
+        let target = match field {
+            "x" => &mut self.xyz.x,
+            "y" => &mut self.xyz.y,
+            "z" => &mut self.xyz.z,
+            "width" => &mut self.scale.x,
+            "height" => &mut self.scale.y,
+            _ => return false,
+        };
+
+        *target = value;
+        true
+    }
+}
+
 impl PositionComponent {
     #[must_use]
     pub fn get_model_matrix(&self) -> Mat4 {
@@ -12,6 +73,42 @@ impl PositionComponent {
     }
 }
 
+// position before the last simulation step, rendering blends from it to the current one
+pub(crate) struct PreviousPosition {
+    pub xyz: Vec3,
+}
+
+impl Component for PreviousPosition {}
+
+// world matrix of a position, relative to all parents, updated before render and hit-testing
+pub struct TransformComponent {
+    pub world: Mat4,
+}
+
+impl Component for TransformComponent {}
+
+// derived from the positions, editing it would be overwritten on the next propagation
+impl Inspect for TransformComponent {
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        let translation = self.world.w_axis;
+
+        vec![(
+            "world",
+            FieldValue::text(format!(
+                "{} {} {}",
+                translation.x, translation.y, translation.z
+            )),
+        )]
+    }
+}
+
+impl TransformComponent {
+    #[must_use]
+    pub fn get_z(&self) -> f32 {
+        self.world.w_axis.z
+    }
+}
+
 pub struct Quad {
     pub top_right: Vec2,
     pub top_left: Vec2,
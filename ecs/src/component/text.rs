@@ -1,7 +1,15 @@
-use super::{ComponentStorage, Entity, Layer};
+use super::{
+    super::{
+        inspect::{FieldValue, Inspect, InspectContext},
+        snapshot::{LoadContext, SaveContext, SavedComponent, SnapshotError},
+    },
+    Component, Layer,
+};
 
 use rendering::ImageData;
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
 pub struct TextContent {
     pub text: String,
     pub font: String,
@@ -12,20 +20,8 @@ pub struct TextComponent {
     pub content: TextContent,
     pub bitmap: Option<ImageData>,
     pub layer: Layer,
-}
-
-impl ComponentStorage<TextComponent> {
-    pub fn destroy(&mut self) {
-        for component in self.components.values_mut() {
-            component.destroy();
-        }
-    }
-
-    pub fn destroy_entity(&mut self, entity: Entity) {
-        if let Some(mut component) = self.components.remove(&entity) {
-            component.destroy();
-        }
-    }
+    // content the bitmap was rasterized from
+    pub(crate) rasterized: Option<TextContent>,
 }
 
 impl TextComponent {
@@ -35,12 +31,61 @@ impl TextComponent {
             content,
             bitmap: None,
             layer: Layer::Interface,
+            rasterized: None,
         }
     }
+}
+
+impl Component for TextComponent {
+    // rasterized again if the component is added back
+    fn release(&mut self, images: &mut Vec<ImageData>) {
+        images.extend(self.bitmap.take());
+        self.rasterized = None;
+    }
+}
 
-    fn destroy(&mut self) {
-        if let Some(image_data) = self.bitmap.take() {
-            image_data.destroy();
+// the bitmap is gpu state, it is rasterized again on the first draw after loading
+#[derive(Deserialize, Serialize)]
+pub struct SavedText {
+    content: TextContent,
+    layer: Layer,
+}
+
+impl SavedComponent for TextComponent {
+    type Saved = SavedText;
+
+    fn save(&self, _context: &SaveContext) -> Option<Self::Saved> {
+        Some(SavedText {
+            content: self.content.clone(),
+            layer: self.layer,
+        })
+    }
+
+    fn load(saved: Self::Saved, _context: &LoadContext) -> Result<Self, SnapshotError> {
+        let mut text = Self::create(saved.content);
+        text.layer = saved.layer;
+
+        Ok(text)
+    }
+}
+
+// editing the font size rasterizes the text again
+impl Inspect for TextComponent {
+    fn inspect(&self, _context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        vec![
+            ("text", FieldValue::text(format!("{:?}", self.content.text))),
+            ("font", FieldValue::text(&self.content.font)),
+            ("font_size", FieldValue::Number(self.content.font_size)),
+            ("layer", FieldValue::text(format!("{:?}", self.layer))),
+        ]
+    }
+
+    fn edit(&mut self, field: &str, value: f32) -> bool {
+        if field != "font_size" {
+            return false;
         }
+
+        self.content.font_size = value.max(1.0);
+        true
     }
 }
@@ -1,35 +1,133 @@
+use super::{
+    super::{
+        inspect::{FieldValue, Inspect, InspectContext},
+        snapshot::{LoadContext, SaveContext, SavedComponent, SnapshotError},
+    },
+    Component,
+};
+
+use serde::{Deserialize, Serialize};
+
 pub struct VisualComponent {
     texture_indices: Vec<usize>,
     current_texture: usize,
     pub layer: Layer,
-    frame_duration: usize,
-    current_frame: usize,
+    // seconds each texture is shown, 0 disables the animation
+    frame_duration: f32,
+    frame_time: f32,
+    visible: bool,
+}
+
+impl Component for VisualComponent {}
+
+// texture indices depend on the texture table, names stay stable
+#[derive(Deserialize, Serialize)]
+pub struct SavedVisual {
+    textures: Vec<String>,
+    layer: Layer,
+    frame_duration: f32,
     visible: bool,
 }
 
+impl SavedComponent for VisualComponent {
+    type Saved = SavedVisual;
+
+    fn save(&self, context: &SaveContext) -> Option<Self::Saved> {
+        let textures = self
+            .texture_indices
+            .iter()
+            .map(|index| context.texture_name(*index).map(str::to_owned))
+            .collect::<Option<_>>()?;
+
+        Some(SavedVisual {
+            textures,
+            layer: self.layer,
+            frame_duration: self.frame_duration,
+            visible: self.visible,
+        })
+    }
+
+    fn load(saved: Self::Saved, context: &LoadContext) -> Result<Self, SnapshotError> {
+        let texture_indices = saved
+            .textures
+            .iter()
+            .map(|name| context.texture_index(name))
+            .collect::<Result<_, _>>()?;
+
+        let mut visual = Self::new(texture_indices, saved.layer, saved.frame_duration);
+        visual.visible = saved.visible;
+
+        Ok(visual)
+    }
+}
+
+impl Inspect for VisualComponent {
+    fn inspect(&self, context: &InspectContext) -> Vec<(&'static str, FieldValue)> {
+        let textures = self
+            .texture_indices
+            .iter()
+            .map(|index| context.texture_name(*index).unwrap_or("?"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        vec![
+            ("layer", FieldValue::text(format!("{:?}", self.layer))),
+            ("textures", FieldValue::Text(textures)),
+            (
+                "current_texture",
+                FieldValue::Number(self.current_texture as f32),
+            ),
+            ("frame_duration", FieldValue::Number(self.frame_duration)),
+            ("frame_time", FieldValue::Number(self.frame_time)),
+            ("visible", FieldValue::text(self.visible)),
+        ]
+    }
+
+    fn edit(&mut self, field: &str, value: f32) -> bool {
+        let value = value.max(0.0);
+
+        match field {
+            "current_texture" => {
+                self.current_texture =
+                    (value as usize).min(self.texture_indices.len().saturating_sub(1));
+            }
+            "frame_duration" => self.frame_duration = value,
+            "frame_time" => self.frame_time = value,
+            _ => return false,
+        }
+
+        true
+    }
+}
+
 impl VisualComponent {
     #[must_use]
-    pub fn new(texture_indices: Vec<usize>, layer: Layer, frame_duration: usize) -> Self {
+    pub fn new(texture_indices: Vec<usize>, layer: Layer, frame_duration: f32) -> Self {
         Self {
             texture_indices,
             current_texture: 0,
             layer,
             frame_duration,
-            current_frame: 0,
+            frame_time: 0.0,
             visible: true,
         }
     }
 
-    pub fn update_animation(&mut self) {
-        if self.frame_duration == 0 || self.texture_indices.len() == 1 {
+    pub fn update_animation(&mut self, delta: f32) {
+        if self.frame_duration <= 0.0 || self.texture_indices.len() <= 1 {
             return;
         }
 
-        self.current_frame += 1;
-        if self.current_frame >= self.frame_duration {
-            self.current_frame = 0;
-            self.current_texture = (self.current_texture + 1) % self.texture_indices.len();
+        self.frame_time += delta;
+        if self.frame_time < self.frame_duration {
+            return;
         }
+
+        // skipped textures are counted at once, tiny durations would loop for ages otherwise
+        let count = self.texture_indices.len();
+        let frames = (self.frame_time / self.frame_duration) as usize;
+        self.current_texture = (self.current_texture + frames % count) % count;
+        self.frame_time %= self.frame_duration;
     }
 
     #[must_use]
@@ -43,6 +141,7 @@ impl VisualComponent {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum Layer {
     Interface,
     Game,
@@ -59,3 +158,36 @@ impl Layer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Layer, VisualComponent};
+
+    #[test]
+    fn animations_advance_by_whole_frames() {
+        let mut visual = VisualComponent::new(vec![4, 5, 6], Layer::Game, 0.5);
+
+        visual.update_animation(0.25);
+        assert_eq!(visual.get_current_texture(), 4);
+
+        visual.update_animation(0.5);
+        assert_eq!(visual.get_current_texture(), 5);
+
+        // two frames at once, 0.25 is left over
+        visual.update_animation(1.0);
+        assert_eq!(visual.get_current_texture(), 4);
+
+        visual.update_animation(0.25);
+        assert_eq!(visual.get_current_texture(), 5);
+    }
+
+    #[test]
+    fn tiny_frame_durations_do_not_loop_per_frame() {
+        let mut visual = VisualComponent::new(vec![4, 5, 6], Layer::Game, 1e-9);
+
+        visual.update_animation(1.0);
+
+        assert!(visual.frame_time < visual.frame_duration);
+        assert!([4, 5, 6].contains(&visual.get_current_texture()));
+    }
+}
@@ -1,23 +1,136 @@
-use std::collections::HashSet;
+use std::{
+    cell::Cell,
+    fmt::{self, Display, Formatter},
+};
 
-pub type Entity = u32;
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    #[must_use]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    #[must_use]
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl Display for Entity {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}v{}", self.index, self.generation)
+    }
+}
+
+struct Slot {
+    generation: u32,
+    alive: bool,
+}
 
 #[derive(Default)]
 pub(crate) struct EntityManager {
-    next_id: Entity,
-    entities: HashSet<Entity>,
+    slots: Vec<Slot>,
+    // indices of destroyed entities, reused before growing slots
+    free_indices: Vec<u32>,
+    // handed out through a shared reference, become alive on the next flush
+    reserved: Cell<u32>,
 }
 
 impl EntityManager {
+    // predicts what create_entity will return after the earlier reservations were flushed
+    pub fn reserve_entity(&self) -> Entity {
+        let reserved = self.reserved.get() as usize;
+        self.reserved.set(self.reserved.get() + 1);
+
+        if let Some(free) = self.free_indices.len().checked_sub(reserved + 1) {
+            let index = self.free_indices[free];
+
+            return Entity {
+                index,
+                generation: self.slots[index as usize].generation,
+            };
+        }
+
+        Entity {
+            index: (self.slots.len() + reserved - self.free_indices.len()) as u32,
+            generation: 0,
+        }
+    }
+
+    pub fn flush(&mut self) {
+        for _ in 0..self.reserved.replace(0) {
+            self.allocate_entity();
+        }
+    }
+
     pub fn create_entity(&mut self) -> Entity {
-        let entity = self.next_id;
-        self.entities.insert(entity);
-        self.next_id += 1;
-        entity
+        // reservations assume nothing was allocated or freed in between
+        self.flush();
+        self.allocate_entity()
+    }
+
+    fn allocate_entity(&mut self) -> Entity {
+        if let Some(index) = self.free_indices.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.alive = true;
+
+            return Entity {
+                index,
+                generation: slot.generation,
+            };
+        }
+
+        let index = self.slots.len() as u32;
+        self.slots.push(Slot {
+            generation: 0,
+            alive: true,
+        });
+
+        Entity {
+            index,
+            generation: 0,
+        }
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.slots
+            .get(entity.index as usize)
+            .is_some_and(|slot| slot.alive && slot.generation == entity.generation)
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.slots.len() - self.free_indices.len()
+    }
+
+    pub fn alive_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.alive)
+            .map(|(index, slot)| Entity {
+                index: index as u32,
+                generation: slot.generation,
+            })
     }
 
     pub fn destroy_entity(&mut self, entity: Entity) {
-        self.entities.remove(&entity);
+        self.flush();
+
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        let slot = &mut self.slots[entity.index as usize];
+        slot.alive = false;
+        // bumping the generation invalidates every handle still pointing to this slot
+        slot.generation = slot.generation.wrapping_add(1);
+
+        self.free_indices.push(entity.index);
     }
 }
 
@@ -25,3 +138,61 @@ pub trait EntityProvider {
     fn get_entities(&self) -> &[Entity];
     fn get_player(&self) -> Option<Entity>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::testing::{self, Health},
+        EntityManager,
+    };
+
+    #[test]
+    fn reused_indices_get_a_new_generation() {
+        let mut manager = EntityManager::default();
+        let first = manager.create_entity();
+        manager.destroy_entity(first);
+
+        let second = manager.create_entity();
+
+        assert_eq!(second.index(), first.index());
+        assert_ne!(second.generation(), first.generation());
+        assert!(!manager.is_alive(first));
+        assert!(manager.is_alive(second));
+    }
+
+    #[test]
+    fn reservations_match_the_flushed_entities() {
+        let mut manager = EntityManager::default();
+        let destroyed = manager.create_entity();
+        manager.create_entity();
+        manager.destroy_entity(destroyed);
+
+        let reused = manager.reserve_entity();
+        let appended = manager.reserve_entity();
+        manager.flush();
+
+        assert!(manager.is_alive(reused));
+        assert!(manager.is_alive(appended));
+        assert_eq!(reused.index(), destroyed.index());
+        assert_eq!(manager.alive_count(), 3);
+    }
+
+    #[test]
+    fn stale_handles_are_rejected() {
+        let mut ecs = testing::ecs();
+        let stale = ecs.create_entity();
+        ecs.add_component(stale, Health(1));
+        ecs.destroy_entity(stale);
+
+        let current = ecs.create_entity();
+        ecs.add_component(stale, Health(2));
+
+        assert_eq!(current.index(), stale.index());
+        assert!(ecs.get_component::<Health>(stale).is_none());
+        assert!(ecs.get_component::<Health>(current).is_none());
+
+        // destroying the stale handle again must not touch the new entity
+        ecs.destroy_entity(stale);
+        assert!(ecs.is_alive(current));
+    }
+}
@@ -0,0 +1,221 @@
+use super::resource::ResourceManager;
+
+use std::{any::TypeId, collections::HashMap, marker::PhantomData, mem};
+
+// double buffered: events stay readable until the second frame with a step after they were sent
+pub struct Events<T> {
+    previous: Vec<T>,
+    current: Vec<T>,
+    // id of the first event in previous, ids keep counting up across updates
+    first_id: usize,
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            first_id: 0,
+        }
+    }
+}
+
+impl<T> Events<T> {
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+
+    #[must_use]
+    pub fn writer(&mut self) -> EventWriter<'_, T> {
+        EventWriter { events: self }
+    }
+
+    // drops the events of the previous frame
+    pub fn update(&mut self) {
+        self.first_id += self.previous.len();
+        self.previous = mem::take(&mut self.current);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn next_id(&self) -> usize {
+        self.first_id + self.len()
+    }
+
+    fn get(&self, id: usize) -> Option<&T> {
+        let index = id.checked_sub(self.first_id)?;
+
+        match index.checked_sub(self.previous.len()) {
+            Some(current_index) => self.current.get(current_index),
+            None => self.previous.get(index),
+        }
+    }
+}
+
+pub struct EventWriter<'events, T> {
+    events: &'events mut Events<T>,
+}
+
+impl<T> EventWriter<'_, T> {
+    pub fn send(&mut self, event: T) {
+        self.events.send(event);
+    }
+
+    pub fn send_batch<I>(&mut self, events: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.events.current.extend(events);
+    }
+}
+
+// each reader keeps its own position, so every reader sees every event once
+pub struct EventReader<T> {
+    next_id: usize,
+    event_type: PhantomData<T>,
+}
+
+impl<T> Default for EventReader<T> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            event_type: PhantomData,
+        }
+    }
+}
+
+impl<T> EventReader<T> {
+    // events that were dropped before the reader got to them are skipped
+    pub fn read<'events>(
+        &mut self,
+        events: &'events Events<T>,
+    ) -> impl Iterator<Item = &'events T> {
+        let start = self.next_id.max(events.first_id);
+        self.next_id = events.next_id();
+
+        (start..self.next_id).filter_map(|id| events.get(id))
+    }
+
+    // marks everything as read without looking at it
+    pub fn clear(&mut self, events: &Events<T>) {
+        self.next_id = events.next_id();
+    }
+}
+
+// swaps the buffers of every registered event type once per frame that runs steps
+#[derive(Default)]
+pub(crate) struct EventManager {
+    updaters: HashMap<TypeId, fn(&mut ResourceManager)>,
+}
+
+impl EventManager {
+    pub fn register<T>(&mut self, resource_manager: &mut ResourceManager)
+    where
+        T: 'static,
+    {
+        if resource_manager.get::<Events<T>>().is_none() {
+            resource_manager.insert(Events::<T>::default());
+        }
+
+        self.updaters
+            .insert(TypeId::of::<Events<T>>(), update_events::<T>);
+    }
+
+    pub fn update(&self, resource_manager: &mut ResourceManager) {
+        for updater in self.updaters.values() {
+            updater(resource_manager);
+        }
+    }
+}
+
+fn update_events<T>(resource_manager: &mut ResourceManager)
+where
+    T: 'static,
+{
+    if let Some(events) = resource_manager.get_mut::<Events<T>>() {
+        events.update();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            entity::EntityProvider,
+            system::{Stage, System},
+            testing::{self, Entities},
+            time::Time,
+            ECS,
+        },
+        EventReader, Events,
+    };
+
+    use std::time::Duration;
+
+    // collects the numbers it reads into the Vec<u32> resource
+    #[derive(Default)]
+    struct ReadNumbers(EventReader<u32>);
+
+    impl System<()> for ReadNumbers {
+        fn run(&mut self, ecs: &mut ECS<()>, _provider: &dyn EntityProvider) {
+            let Some(events) = ecs.events::<u32>() else {
+                return;
+            };
+            let read: Vec<u32> = self.0.read(events).copied().collect();
+
+            if let Some(numbers) = ecs.resource_mut::<Vec<u32>>() {
+                numbers.extend(read);
+            }
+        }
+    }
+
+    #[test]
+    fn events_are_kept_for_two_frames() {
+        let mut ecs = testing::ecs();
+        // every update is due at least one step
+        ecs.insert_resource(Time::new(Duration::from_nanos(1)));
+        let mut reader = EventReader::<u32>::default();
+
+        ecs.send_event(1_u32);
+        ecs.update(&Entities(Vec::new()));
+        ecs.send_event(2_u32);
+
+        let events = ecs.events::<u32>().expect("Events were not registered!");
+        assert_eq!(reader.read(events).copied().collect::<Vec<_>>(), [1, 2]);
+
+        ecs.update(&Entities(Vec::new()));
+        ecs.update(&Entities(Vec::new()));
+        assert!(ecs.events::<u32>().is_some_and(Events::is_empty));
+    }
+
+    #[test]
+    fn events_survive_until_the_next_step() {
+        let mut ecs = testing::ecs();
+        ecs.insert_resource(Vec::<u32>::new());
+        ecs.add_system(Stage::PreUpdate, ReadNumbers::default());
+
+        let mut time = Time::new(Duration::from_nanos(1));
+        time.set_paused(true);
+        ecs.insert_resource(time);
+
+        ecs.send_event(1_u32);
+        ecs.update(&Entities(Vec::new()));
+        ecs.update(&Entities(Vec::new()));
+        assert_eq!(ecs.resource::<Vec<u32>>(), Some(&Vec::new()));
+
+        if let Some(time) = ecs.resource_mut::<Time>() {
+            time.set_paused(false);
+        }
+        ecs.update(&Entities(Vec::new()));
+
+        assert_eq!(ecs.resource::<Vec<u32>>(), Some(&vec![1]));
+    }
+}
@@ -0,0 +1,225 @@
+use super::{
+    component::{
+        ChildrenComponent, Component, ComponentManager, DisabledComponent, InputComponent,
+        ParentComponent, PhysicsComponent, PositionComponent, TagComponent, TextComponent,
+        TransformComponent, VisualComponent,
+    },
+    entity::Entity,
+    system::ResourceSystem,
+};
+
+use std::fmt::{self, Display, Formatter};
+
+// only numbers can be edited, everything else is already formatted for display
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    Number(f32),
+    Text(String),
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            FieldValue::Number(number) => write!(formatter, "{number}"),
+            FieldValue::Text(text) => write!(formatter, "{text}"),
+        }
+    }
+}
+
+impl FieldValue {
+    pub fn text<T>(value: T) -> Self
+    where
+        T: Display,
+    {
+        Self::Text(value.to_string())
+    }
+
+    pub fn entities(entities: &[Entity]) -> Self {
+        Self::Text(
+            entities
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+
+    pub fn optional_entity(entity: Option<Entity>) -> Self {
+        entity.map_or_else(|| Self::text("none"), Self::text)
+    }
+}
+
+pub struct InspectContext<'inspect> {
+    resource_system: &'inspect ResourceSystem,
+}
+
+impl InspectContext<'_> {
+    #[must_use]
+    pub fn texture_name(&self, texture_index: usize) -> Option<&str> {
+        self.resource_system.get_texture_name(texture_index)
+    }
+}
+
+pub trait Inspect: Component + Sized {
+    fn inspect(&self, context: &InspectContext) -> Vec<(&'static str, FieldValue)>;
+
+    // false if the field does not exist or can not be edited
+    fn edit(&mut self, _field: &str, _value: f32) -> bool {
+        false
+    }
+}
+
+pub struct InspectedComponent {
+    pub key: String,
+    pub fields: Vec<(&'static str, FieldValue)>,
+}
+
+pub struct InspectedEntity {
+    pub entity: Entity,
+    pub name: Option<String>,
+    pub components: Vec<InspectedComponent>,
+}
+
+impl Display for InspectedEntity {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) => writeln!(formatter, "entity {} \"{name}\"", self.entity)?,
+            None => writeln!(formatter, "entity {}", self.entity)?,
+        }
+
+        for component in &self.components {
+            let fields = component
+                .fields
+                .iter()
+                .map(|(field, value)| format!("{field}: {value}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            writeln!(formatter, "  {} {{ {fields} }}", component.key)?;
+        }
+
+        Ok(())
+    }
+}
+
+type InspectFn =
+    fn(&ComponentManager, Entity, &InspectContext) -> Option<Vec<(&'static str, FieldValue)>>;
+type EditFn = fn(&mut ComponentManager, Entity, &str, f32) -> bool;
+
+struct InspectEntry {
+    key: String,
+    inspect: InspectFn,
+    edit: EditFn,
+}
+
+pub(crate) struct InspectRegistry {
+    entries: Vec<InspectEntry>,
+}
+
+impl InspectRegistry {
+    pub fn create<E>() -> Self
+    where
+        E: 'static,
+    {
+        let mut registry = Self {
+            entries: Vec::with_capacity(10),
+        };
+
+        registry.register::<PositionComponent>("position");
+        registry.register::<TransformComponent>("transform");
+        registry.register::<PhysicsComponent>("physics");
+        registry.register::<VisualComponent>("visual");
+        registry.register::<TextComponent>("text");
+        registry.register::<InputComponent<E>>("input");
+        registry.register::<ParentComponent>("parent");
+        registry.register::<ChildrenComponent>("children");
+        registry.register::<TagComponent>("tags");
+        registry.register::<DisabledComponent>("disabled");
+
+        registry
+    }
+
+    pub fn register<T>(&mut self, key: &str)
+    where
+        T: Inspect,
+    {
+        self.entries.retain(|entry| entry.key != key);
+        self.entries.push(InspectEntry {
+            key: key.to_owned(),
+            inspect: inspect_component::<T>,
+            edit: edit_component::<T>,
+        });
+    }
+
+    pub fn inspect(
+        &self,
+        component_manager: &ComponentManager,
+        resource_system: &ResourceSystem,
+        entity: Entity,
+        name: Option<String>,
+    ) -> InspectedEntity {
+        let context = InspectContext { resource_system };
+
+        let components = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                Some(InspectedComponent {
+                    key: entry.key.clone(),
+                    fields: (entry.inspect)(component_manager, entity, &context)?,
+                })
+            })
+            .collect();
+
+        InspectedEntity {
+            entity,
+            name,
+            components,
+        }
+    }
+
+    pub fn edit(
+        &self,
+        component_manager: &mut ComponentManager,
+        entity: Entity,
+        key: &str,
+        field: &str,
+        value: f32,
+    ) -> bool {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .is_some_and(|entry| (entry.edit)(component_manager, entity, field, value))
+    }
+}
+
+fn inspect_component<T>(
+    component_manager: &ComponentManager,
+    entity: Entity,
+    context: &InspectContext,
+) -> Option<Vec<(&'static str, FieldValue)>>
+where
+    T: Inspect,
+{
+    Some(
+        component_manager
+            .storage::<T>()?
+            .get(entity)?
+            .inspect(context),
+    )
+}
+
+fn edit_component<T>(
+    component_manager: &mut ComponentManager,
+    entity: Entity,
+    field: &str,
+    value: f32,
+) -> bool
+where
+    T: Inspect,
+{
+    component_manager
+        .storage_mut::<T>()
+        .and_then(|storage| storage.get_mut(entity))
+        .is_some_and(|component| component.edit(field, value))
+}
@@ -1,11 +1,32 @@
+mod command;
 mod component;
 mod entity;
+mod event;
+mod inspect;
+mod pool;
+mod query;
+mod random;
+mod resource;
+mod snapshot;
 mod system;
+#[cfg(test)]
+mod testing;
+mod time;
 
-use component::ComponentManager;
+use command::CommandQueue;
+use component::{ComponentManager, DisabledComponent, NameIndex};
 use entity::EntityManager;
+use event::EventManager;
+use indexmap::IndexSet;
+use inspect::InspectRegistry;
 use rendering::{Renderer, WindowSize};
-use system::{InputSystem, MouseHandler, MousePosition, RenderSystem, SystemManager};
+use resource::ResourceManager;
+use serde::{de::DeserializeOwned, Serialize};
+use snapshot::SnapshotRegistry;
+use std::{marker::PhantomData, mem, time::Instant};
+use system::{
+    HierarchySystem, InputSystem, MouseHandler, MousePosition, SystemManager, NOT_FOUND_INDEX,
+};
 
 use winit::{
     dpi::PhysicalPosition,
@@ -14,39 +35,140 @@ use winit::{
     keyboard::Key,
 };
 
+pub use command::Commands;
 pub use component::{
-    Component, InputComponent, Layer, PhysicsComponent, PositionComponent, TextComponent,
-    TextContent, VisualComponent,
+    ChildrenComponent, Component, InputComponent, Layer, NameComponent, ParentComponent,
+    PhysicsComponent, PositionComponent, TagComponent, TextComponent, TextContent,
+    TransformComponent, VisualComponent,
 };
 pub use entity::{Entity, EntityProvider};
-pub use system::{InputHandler, MouseEvent};
+pub use event::{EventReader, EventWriter, Events};
+pub use inspect::{FieldValue, Inspect, InspectContext, InspectedComponent, InspectedEntity};
+pub use pool::EntityPool;
+pub use query::{Added, Changed, Mut, Query, QueryData};
+pub use random::Random;
+pub use snapshot::{
+    LoadContext, LoadedSnapshot, SaveContext, SavedComponent, Snapshot, SnapshotError,
+};
+pub use system::{
+    AnimationSystem, InputHandler, MouseEvent, MovementSystem, RenderTimings, ResourceError,
+    ScreenEffect, Stage, System, SystemConfig,
+};
+pub use time::{Time, Timer};
 
 pub struct ECS<E>
 where
     E: 'static,
 {
     entity_manager: EntityManager,
-    component_manager: ComponentManager<E>,
-    system_manager: SystemManager,
+    component_manager: ComponentManager,
+    name_index: NameIndex,
+    system_manager: SystemManager<E>,
+    resource_manager: ResourceManager,
+    event_manager: EventManager,
+    snapshot_registry: SnapshotRegistry,
+    inspect_registry: InspectRegistry,
+    command_queue: CommandQueue<E>,
+    event_type: PhantomData<E>,
 }
 
 impl<E> ECS<E>
 where
     E: 'static,
 {
-    #[must_use]
-    pub fn create(texture_path: &str, font_path: &str) -> Self {
-        Self {
+    // fails if the texture table can not be read, missing textures and fonts only log a warning
+    pub fn create(texture_path: &str, font_path: &str) -> Result<Self, ResourceError> {
+        let mut resource_manager = ResourceManager::default();
+        resource_manager.insert(Time::default());
+
+        Ok(Self {
             entity_manager: EntityManager::default(),
-            component_manager: ComponentManager::create(),
-            system_manager: SystemManager::create(texture_path, font_path),
-        }
+            component_manager: ComponentManager::create::<E>(),
+            name_index: NameIndex::default(),
+            system_manager: SystemManager::create(texture_path, font_path)?,
+            resource_manager,
+            event_manager: EventManager::default(),
+            snapshot_registry: SnapshotRegistry::default(),
+            inspect_registry: InspectRegistry::create::<E>(),
+            command_queue: CommandQueue::default(),
+            event_type: PhantomData,
+        })
     }
 
     pub fn create_entity(&mut self) -> Entity {
         self.entity_manager.create_entity()
     }
 
+    #[must_use]
+    pub fn commands(&self) -> Commands<'_, E> {
+        Commands::new(&self.command_queue, &self.entity_manager)
+    }
+
+    // sync point: reserved entities become alive and queued commands run in order
+    pub fn apply_commands(&mut self) {
+        // commands may queue further commands, including spawns that have to be alive
+        // before the inserts of the next batch run
+        loop {
+            self.entity_manager.flush();
+            if self.command_queue.is_empty() {
+                break;
+            }
+
+            for command in self.command_queue.take() {
+                command(self);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entity_manager.is_alive(entity)
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entity_manager.alive_entities()
+    }
+
+    #[must_use]
+    pub fn entity_count(&self) -> usize {
+        self.entity_manager.alive_count()
+    }
+
+    // disabled entities keep their components but are skipped by queries, rendering and
+    // hit-testing, children are disabled with their parent
+    pub fn disable_entity(&mut self, entity: Entity) {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        let entities = HierarchySystem::with_descendants(&self.component_manager, &[entity]);
+        let storage = self.component_manager.register::<DisabledComponent>();
+
+        for disabled in entities {
+            storage.add(disabled, DisabledComponent);
+        }
+    }
+
+    pub fn enable_entity(&mut self, entity: Entity) {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        let entities = HierarchySystem::with_descendants(&self.component_manager, &[entity]);
+        let Some(storage) = self.component_manager.storage_mut::<DisabledComponent>() else {
+            return;
+        };
+
+        for enabled in entities {
+            storage.remove(enabled);
+        }
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self, entity: Entity) -> bool {
+        self.is_alive(entity) && self.get_component::<DisabledComponent>(entity).is_none()
+    }
+
     pub fn initialize<R>(&mut self, renderer: &R)
     where
         R: Renderer,
@@ -54,29 +176,373 @@ where
         self.system_manager.initialize(renderer);
     }
 
-    pub fn add_component(&mut self, entity: Entity, component: Component<E>) {
-        match component {
-            Component::Position(position_component) => self
-                .component_manager
-                .position_storage
-                .add(entity, position_component),
-            Component::Visual(visual_component) => self
-                .component_manager
-                .visual_storage
-                .add(entity, visual_component),
-            Component::Text(text_component) => self
-                .component_manager
-                .text_storage
-                .add(entity, text_component),
-            Component::Input(input_component) => self
+    pub fn register_component<T>(&mut self)
+    where
+        T: Component,
+    {
+        self.component_manager.register::<T>();
+    }
+
+    pub fn add_component<T>(&mut self, entity: Entity, component: T)
+    where
+        T: Component,
+    {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        self.component_manager
+            .register::<T>()
+            .add(entity, component);
+    }
+
+    #[must_use]
+    pub fn get_component<T>(&self, entity: Entity) -> Option<&T>
+    where
+        T: Component,
+    {
+        self.component_manager.storage::<T>()?.get(entity)
+    }
+
+    pub fn get_component_mut<T>(&mut self, entity: Entity) -> Option<&mut T>
+    where
+        T: Component,
+    {
+        self.component_manager.storage_mut::<T>()?.get_mut(entity)
+    }
+
+    pub fn remove_component<T>(&mut self, entity: Entity) -> Option<T>
+    where
+        T: Component,
+    {
+        self.component_manager.storage_mut::<T>()?.remove(entity)
+    }
+
+    // replaces a previous name, names do not have to be unique
+    pub fn set_name(&mut self, entity: Entity, name: &str) {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        if let Some(previous) = self.remove_component::<NameComponent>(entity) {
+            self.name_index.remove_name(entity, previous.get());
+        }
+
+        self.add_component(entity, NameIndex::create_name(name.to_owned()));
+        self.name_index.insert_name(entity, name);
+    }
+
+    #[must_use]
+    pub fn get_name(&self, entity: Entity) -> Option<&str> {
+        self.get_component::<NameComponent>(entity)
+            .map(NameComponent::get)
+    }
+
+    // the first entity that was given this name and still has it
+    #[must_use]
+    pub fn find_by_name(&self, name: &str) -> Option<Entity> {
+        self.name_index
+            .named(name)
+            .iter()
+            .copied()
+            .find(|entity| self.is_alive(*entity) && self.get_name(*entity) == Some(name))
+    }
+
+    pub fn add_tag(&mut self, entity: Entity, tag: &str) {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        let tag_storage = self.component_manager.register::<TagComponent>();
+        if tag_storage.get(entity).is_none() {
+            tag_storage.add(entity, TagComponent::default());
+        }
+
+        let Some(tags) = tag_storage.get_mut(entity) else {
+            return;
+        };
+
+        self.name_index.add_tag(entity, tags, tag);
+    }
+
+    pub fn remove_tag(&mut self, entity: Entity, tag: &str) {
+        let Some(tags) = self
+            .component_manager
+            .storage_mut::<TagComponent>()
+            .and_then(|tag_storage| tag_storage.get_mut(entity))
+        else {
+            return;
+        };
+
+        self.name_index.remove_tag(entity, tags, tag);
+    }
+
+    pub fn entities_with_tag<'ecs>(
+        &'ecs self,
+        tag: &'ecs str,
+    ) -> impl Iterator<Item = Entity> + 'ecs {
+        self.name_index
+            .tagged(tag)
+            .iter()
+            .copied()
+            .filter(move |entity| {
+                self.is_alive(*entity)
+                    && self
+                        .get_component::<TagComponent>(*entity)
+                        .is_some_and(|tags| tags.has(tag))
+            })
+    }
+
+    // replaces and returns the previous resource of the same type
+    pub fn insert_resource<T>(&mut self, resource: T) -> Option<T>
+    where
+        T: 'static,
+    {
+        self.resource_manager.insert(resource)
+    }
+
+    pub fn remove_resource<T>(&mut self) -> Option<T>
+    where
+        T: 'static,
+    {
+        self.resource_manager.remove()
+    }
+
+    #[must_use]
+    pub fn resource<T>(&self) -> Option<&T>
+    where
+        T: 'static,
+    {
+        self.resource_manager.get()
+    }
+
+    pub fn resource_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: 'static,
+    {
+        self.resource_manager.get_mut()
+    }
+
+    // events of type T are kept for two frames that run steps, see Events
+    pub fn add_event<T>(&mut self)
+    where
+        T: 'static,
+    {
+        self.event_manager.register::<T>(&mut self.resource_manager);
+    }
+
+    pub fn send_event<T>(&mut self, event: T)
+    where
+        T: 'static,
+    {
+        self.event_writer::<T>().send(event);
+    }
+
+    pub fn event_writer<T>(&mut self) -> EventWriter<'_, T>
+    where
+        T: 'static,
+    {
+        self.add_event::<T>();
+
+        self.resource_manager
+            .get_mut::<Events<T>>()
+            .expect("Events were lost after adding them!")
+            .writer()
+    }
+
+    #[must_use]
+    pub fn events<T>(&self) -> Option<&Events<T>>
+    where
+        T: 'static,
+    {
+        self.resource_manager.get()
+    }
+
+    // the key identifies the component in snapshots and must not change between versions
+    pub fn register_saved<T>(&mut self, key: &str)
+    where
+        T: SavedComponent,
+    {
+        self.snapshot_registry.register::<T>(key);
+    }
+
+    // for components that serde can save as they are
+    pub fn register_saved_serde<T>(&mut self, key: &str)
+    where
+        T: Component + Serialize + DeserializeOwned,
+    {
+        self.snapshot_registry.register_serde::<T>(key);
+    }
+
+    pub fn register_saved_resource<T>(&mut self, key: &str)
+    where
+        T: Serialize + DeserializeOwned + 'static,
+    {
+        self.snapshot_registry.register_resource::<T>(key);
+    }
+
+    pub fn register_inspected<T>(&mut self, key: &str)
+    where
+        T: Inspect,
+    {
+        self.inspect_registry.register::<T>(key);
+    }
+
+    #[must_use]
+    pub fn inspect_entity(&self, entity: Entity) -> Option<InspectedEntity> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+
+        Some(self.inspect_registry.inspect(
+            &self.component_manager,
+            &self.system_manager.resource_system,
+            entity,
+            self.get_name(entity).map(str::to_owned),
+        ))
+    }
+
+    #[must_use]
+    pub fn inspect_world(&self) -> Vec<InspectedEntity> {
+        self.entities()
+            .filter_map(|entity| self.inspect_entity(entity))
+            .collect()
+    }
+
+    // plain text listing of every live entity, for stdout or a file
+    #[must_use]
+    pub fn dump_world(&self) -> String {
+        self.inspect_world()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    // only numeric fields can be edited, see Inspect::edit
+    pub fn edit_field(&mut self, entity: Entity, key: &str, field: &str, value: f32) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        self.inspect_registry
+            .edit(&mut self.component_manager, entity, key, field, value)
+    }
+
+    // saves the provided entities with their descendants and all registered resources
+    pub fn save_snapshot<P>(&self, provider: &P) -> Result<Snapshot, SnapshotError>
+    where
+        P: EntityProvider,
+    {
+        let entities: Vec<Entity> =
+            HierarchySystem::with_descendants(&self.component_manager, provider.get_entities())
+                .into_iter()
+                .filter(|entity| self.is_alive(*entity))
+                .collect::<IndexSet<Entity>>()
+                .into_iter()
+                .collect();
+
+        self.snapshot_registry.save(
+            &self.component_manager,
+            &self.resource_manager,
+            &self.system_manager.resource_system,
+            &entities,
+            provider.get_entities(),
+            provider.get_player(),
+        )
+    }
+
+    // spawns new entities for the snapshot, nothing is spawned or replaced if loading fails
+    pub fn load_snapshot(&mut self, snapshot: &Snapshot) -> Result<LoadedSnapshot, SnapshotError> {
+        let entities: Vec<Entity> = (0..snapshot.entity_count())
+            .map(|_| self.create_entity())
+            .collect();
+
+        let loaded = self.snapshot_registry.load(
+            &mut self.component_manager,
+            &mut self.resource_manager,
+            &self.system_manager.resource_system,
+            snapshot,
+            &entities,
+        );
+
+        if loaded.is_err() {
+            for entity in &entities {
+                self.component_manager.clear_entity(*entity);
+                self.entity_manager.destroy_entity(*entity);
+            }
+            return loaded;
+        }
+
+        // relations and the name index are not part of the loaded components
+        for entity in &entities {
+            if let Some(parent) = self.remove_component::<ParentComponent>(*entity) {
+                HierarchySystem::attach(&mut self.component_manager, *entity, parent.get());
+            }
+
+            if let Some(name) = self
                 .component_manager
-                .input_storage
-                .add(entity, input_component),
-            Component::Physics(physics_component) => self
+                .storage::<NameComponent>()
+                .and_then(|name_storage| name_storage.get(*entity))
+            {
+                self.name_index.insert_name(*entity, name.get());
+            }
+
+            if let Some(tags) = self
                 .component_manager
-                .physics_storage
-                .add(entity, physics_component),
+                .storage::<TagComponent>()
+                .and_then(|tag_storage| tag_storage.get(*entity))
+            {
+                self.name_index.insert_tags(*entity, tags);
+            }
+        }
+
+        loaded
+    }
+
+    pub fn query<Q>(&mut self) -> Query<'_, Q>
+    where
+        Q: QueryData,
+    {
+        Query::new(&mut self.component_manager, None)
+    }
+
+    pub fn query_in<'world, Q, P>(&'world mut self, provider: &P) -> Query<'world, Q>
+    where
+        Q: QueryData,
+        P: EntityProvider + ?Sized,
+    {
+        let entities =
+            HierarchySystem::with_descendants(&self.component_manager, provider.get_entities());
+
+        Query::new(&mut self.component_manager, Some(&entities))
+    }
+
+    // the child position becomes relative to the parent, cycles are rejected
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        if !self.is_alive(child) || !self.is_alive(parent) || child == parent {
+            return;
+        }
+
+        if HierarchySystem::is_ancestor(&self.component_manager, child, parent) {
+            return;
         }
+
+        HierarchySystem::detach(&mut self.component_manager, child);
+        HierarchySystem::attach(&mut self.component_manager, child, parent);
+    }
+
+    pub fn remove_parent(&mut self, child: Entity) {
+        HierarchySystem::detach(&mut self.component_manager, child);
+    }
+
+    #[must_use]
+    pub fn get_parent(&self, entity: Entity) -> Option<Entity> {
+        HierarchySystem::get_parent(&self.component_manager, entity)
+    }
+
+    #[must_use]
+    pub fn get_children(&self, entity: Entity) -> &[Entity] {
+        HierarchySystem::get_children(&self.component_manager, entity)
     }
 
     #[must_use]
@@ -84,40 +550,81 @@ where
         self.system_manager.resource_system.get_texture_count()
     }
 
+    // unknown names get the 404 texture, so a typo does not stop the game
     #[must_use]
     pub fn get_texture_index(&self, texture_name: &str) -> usize {
         self.system_manager
             .resource_system
             .get_texture_index(texture_name)
+            .unwrap_or_else(|error| {
+                log::warn!("{error}, using the 404 texture");
+                NOT_FOUND_INDEX
+            })
     }
 
     #[must_use]
+    pub fn find_texture_index(&self, texture_name: &str) -> Option<usize> {
+        self.system_manager
+            .resource_system
+            .find_texture_index(texture_name)
+    }
+
+    #[must_use]
+    // disabled entities keep their focus but are skipped until they are enabled again
     pub fn get_active_entity(&self) -> Option<&Entity> {
-        self.component_manager.input_storage.get_active_entity()
+        let storage = self.component_manager.storage::<InputComponent<E>>()?;
+
+        storage.entities().iter().find(|entity| {
+            self.is_enabled(**entity) && storage.get(**entity).is_some_and(|input| input.is_active)
+        })
     }
 
     pub fn set_next_of(&mut self, current: Entity, next: Entity) {
-        self.component_manager
-            .input_storage
-            .set_next_of(current, next);
+        if !self.is_alive(current) || !self.is_alive(next) {
+            return;
+        }
+
+        let Some(input_storage) = self.component_manager.storage_mut::<InputComponent<E>>() else {
+            return;
+        };
+
+        input_storage.set_next_of(current, next);
     }
 
     pub fn set_previous_of(&mut self, current: Entity, previous: Entity) {
-        self.component_manager
-            .input_storage
-            .set_previous_of(current, previous);
+        if !self.is_alive(current) || !self.is_alive(previous) {
+            return;
+        }
+
+        let Some(input_storage) = self.component_manager.storage_mut::<InputComponent<E>>() else {
+            return;
+        };
+
+        input_storage.set_previous_of(current, previous);
     }
 
     pub fn set_next_active(&mut self, currently_active: Entity) {
-        InputSystem::set_next_entity_to_active(&mut self.component_manager, currently_active);
+        InputSystem::set_next_entity_to_active::<E>(
+            &mut self.component_manager,
+            &self.entity_manager,
+            currently_active,
+        );
     }
 
     pub fn set_previous_active(&mut self, currently_active: Entity) {
-        InputSystem::set_previous_entity_to_active(&mut self.component_manager, currently_active);
+        InputSystem::set_previous_entity_to_active::<E>(
+            &mut self.component_manager,
+            &self.entity_manager,
+            currently_active,
+        );
     }
 
     #[must_use]
     pub fn position_matches_entity(&self, position: &MousePosition, entity: Entity) -> bool {
+        if !self.is_enabled(entity) {
+            return false;
+        }
+
         self.system_manager.input_system.entity_was_clicked(
             &self.component_manager,
             position,
@@ -126,7 +633,11 @@ where
     }
 
     pub fn activate_entity(&self, entity: &Entity, event_proxy: &EventLoopProxy<E>) {
-        if let Some(active_input) = self.component_manager.input_storage.get(*entity) {
+        if !self.is_alive(*entity) {
+            return;
+        }
+
+        if let Some(active_input) = self.get_component::<InputComponent<E>>(*entity) {
             (active_input.activate)(event_proxy);
         }
     }
@@ -164,6 +675,10 @@ where
         handler: &T,
         event_proxy: &EventLoopProxy<E>,
     ) {
+        // hit-testing needs the world positions, as they are rendered
+        let alpha = self.render_alpha();
+        HierarchySystem::propagate_transforms(&mut self.component_manager, alpha);
+
         handler.handle_mouse_events(
             self,
             &self.system_manager.input_system.mouse_inputs,
@@ -179,7 +694,13 @@ where
             event_proxy,
         );
 
-        // clear each frame
+        self.discard_inputs();
+
+        self.apply_commands();
+    }
+
+    // clears the inputs of this frame without handling them, e.g. while input is blocked
+    pub fn discard_inputs(&mut self) {
         self.system_manager.input_system.mouse_inputs.clear();
         self.system_manager
             .input_system
@@ -187,40 +708,127 @@ where
             .clear();
     }
 
-    pub fn update_positions<P>(&mut self, provider: &P)
+    pub fn add_system<S>(&mut self, stage: Stage, system: S) -> SystemConfig<'_, E>
+    where
+        S: System<E>,
+    {
+        self.system_manager.schedule.add_system(stage, system)
+    }
+
+    // removes every system of type S from all stages
+    pub fn remove_system<S>(&mut self)
+    where
+        S: System<E>,
+    {
+        self.system_manager.schedule.remove_system::<S>();
+    }
+
+    pub fn run_stage<P>(&mut self, stage: Stage, provider: &P)
     where
         P: EntityProvider,
     {
-        for entity in provider.get_entities() {
-            let Some(entity_physics) = self.component_manager.physics_storage.get(*entity) else {
-                continue;
-            };
+        // systems need the whole ecs, so the schedule is taken out while they run
+        let mut schedule = mem::take(&mut self.system_manager.schedule);
+        schedule.run(stage, self, provider);
 
-            if let Some(entity_position) = self.component_manager.position_storage.get_mut(*entity)
-            {
-                entity_position.xyz += entity_physics.velocity;
-            };
+        let added = mem::replace(&mut self.system_manager.schedule, schedule);
+        self.system_manager.schedule.append(added);
+
+        self.apply_commands();
+    }
+
+    // runs as many fixed steps as are due since the previous update, see Time
+    pub fn update<P>(&mut self, provider: &P)
+    where
+        P: EntityProvider,
+    {
+        let steps = self.resource_mut::<Time>().map_or(1, Time::advance);
+
+        // once per frame with steps, every system runs at least once before an event is dropped
+        if steps > 0 {
+            self.event_manager.update(&mut self.resource_manager);
+        }
+
+        for _ in 0..steps {
+            self.step(provider);
+        }
+    }
+
+    // a single simulation step, independent of real time, events are only swapped by update
+    pub fn step<P>(&mut self, provider: &P)
+    where
+        P: EntityProvider,
+    {
+        HierarchySystem::record_positions(&mut self.component_manager);
+
+        if let Some(time) = self.resource_mut::<Time>() {
+            time.tick();
         }
+
+        for stage in Stage::UPDATES {
+            self.run_stage(stage, provider);
+        }
+    }
+
+    fn render_alpha(&self) -> f32 {
+        self.resource::<Time>().map_or(1.0, Time::alpha)
     }
 
-    pub fn render<R, P>(&mut self, renderer: &mut R, provider: &P)
+    pub fn render<R, P>(&mut self, renderer: &mut R, provider: &P) -> RenderTimings
     where
         P: EntityProvider,
         R: Renderer,
     {
-        RenderSystem::draw(
+        let start = Instant::now();
+
+        for image_data in self.component_manager.take_released() {
+            renderer.destroy_image_data(image_data);
+        }
+
+        self.run_stage(Stage::Render, provider);
+
+        let alpha = self.render_alpha();
+        HierarchySystem::propagate_transforms(&mut self.component_manager, alpha);
+
+        let screen_effect = self.resource::<ScreenEffect>().copied().unwrap_or_default();
+
+        let submit = self.system_manager.render_system.draw(
             renderer,
             provider,
-            &mut self.component_manager.visual_storage,
-            &mut self.component_manager.text_storage,
-            &self.component_manager.position_storage,
+            &mut self.component_manager,
             &mut self.system_manager.resource_system,
+            screen_effect,
         );
+
+        RenderTimings {
+            prep: start.elapsed().saturating_sub(submit),
+            submit,
+        }
     }
 
     pub fn destroy_entity(&mut self, entity: Entity) {
-        self.component_manager.clear_entity(entity);
-        self.entity_manager.destroy_entity(entity);
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        let mut descendants = Vec::new();
+        HierarchySystem::push_descendants(&self.component_manager, entity, &mut descendants);
+        HierarchySystem::detach(&mut self.component_manager, entity);
+
+        // children are despawned with their parent
+        for destroyed in descendants.into_iter().chain([entity]) {
+            self.name_index.remove_entity(
+                destroyed,
+                self.component_manager
+                    .storage::<NameComponent>()
+                    .and_then(|name_storage| name_storage.get(destroyed)),
+                self.component_manager
+                    .storage::<TagComponent>()
+                    .and_then(|tag_storage| tag_storage.get(destroyed)),
+            );
+            self.component_manager.clear_entity(destroyed);
+            self.entity_manager.destroy_entity(destroyed);
+        }
     }
 
     pub fn destroy(&mut self) {
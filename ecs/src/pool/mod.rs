@@ -0,0 +1,134 @@
+use super::{entity::Entity, ECS};
+
+// reuses disabled entities instead of destroying them, components and gpu resources stay intact
+#[derive(Default)]
+pub struct EntityPool {
+    free: Vec<Entity>,
+}
+
+impl EntityPool {
+    // a reused entity keeps the state it was released with, callers reset what they need
+    pub fn acquire<E, F>(&mut self, ecs: &mut ECS<E>, create: F) -> Entity
+    where
+        E: 'static,
+        F: FnOnce(&mut ECS<E>) -> Entity,
+    {
+        self.reuse(ecs).unwrap_or_else(|| create(ecs))
+    }
+
+    // None if the pool has nothing left to hand out
+    pub fn reuse<E>(&mut self, ecs: &mut ECS<E>) -> Option<Entity>
+    where
+        E: 'static,
+    {
+        // pooled entities may have been destroyed directly, e.g. together with a parent
+        while let Some(entity) = self.free.pop() {
+            if ecs.is_alive(entity) {
+                ecs.enable_entity(entity);
+                return Some(entity);
+            }
+        }
+
+        None
+    }
+
+    pub fn release<E>(&mut self, ecs: &mut ECS<E>, entity: Entity)
+    where
+        E: 'static,
+    {
+        // disabled entities are already waiting in this or another pool
+        if !ecs.is_enabled(entity) {
+            return;
+        }
+
+        ecs.disable_entity(entity);
+        self.free.push(entity);
+    }
+
+    // destroys every pooled entity, e.g. when the scene that used the pool ends
+    pub fn clear<E>(&mut self, ecs: &mut ECS<E>)
+    where
+        E: 'static,
+    {
+        for entity in self.free.drain(..) {
+            ecs.destroy_entity(entity);
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            testing::{self, Armor, Health},
+            ECS,
+        },
+        EntityPool,
+    };
+
+    #[test]
+    fn released_entities_are_skipped_with_their_children() {
+        let mut ecs = testing::ecs();
+        let mut pool = EntityPool::default();
+        let parent = ecs.create_entity();
+        ecs.add_component(parent, Health(1));
+        let child = ecs.create_entity();
+        ecs.add_component(child, Health(2));
+        ecs.set_parent(child, parent);
+
+        pool.release(&mut ecs, parent);
+
+        assert_eq!(pool.len(), 1);
+        assert!(!ecs.is_enabled(parent));
+        assert!(!ecs.is_enabled(child));
+        assert_eq!(ecs.query::<&Health>().count(), 0);
+
+        // releasing twice does not pool it twice
+        pool.release(&mut ecs, parent);
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn acquire_reuses_entities_with_their_components() {
+        let mut ecs = testing::ecs();
+        let mut pool = EntityPool::default();
+        let entity = ecs.create_entity();
+        ecs.add_component(entity, Health(7));
+        ecs.add_component(entity, Armor(1));
+
+        pool.release(&mut ecs, entity);
+        let acquired = pool.acquire(&mut ecs, |_| panic!("The pooled entity was not reused!"));
+
+        assert_eq!(acquired, entity);
+        assert!(pool.is_empty());
+        assert!(ecs.is_enabled(entity));
+        assert_eq!(ecs.get_component::<Health>(entity), Some(&Health(7)));
+        assert_eq!(ecs.get_component::<Armor>(entity), Some(&Armor(1)));
+        assert_eq!(ecs.query::<&Health>().count(), 1);
+    }
+
+    #[test]
+    fn destroyed_entities_are_not_handed_out() {
+        let mut ecs = testing::ecs();
+        let mut pool = EntityPool::default();
+        let entity = ecs.create_entity();
+
+        pool.release(&mut ecs, entity);
+        ecs.destroy_entity(entity);
+
+        let acquired = pool.acquire(&mut ecs, ECS::create_entity);
+        assert_ne!(acquired, entity);
+        assert!(ecs.is_alive(acquired));
+        assert!(pool.reuse(&mut ecs).is_none());
+    }
+}
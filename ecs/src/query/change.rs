@@ -0,0 +1,263 @@
+use super::{
+    super::{
+        component::{Component, ComponentManager, ComponentStorage, ComponentTicks},
+        entity::Entity,
+    },
+    QueryData,
+};
+
+use std::{
+    any::TypeId,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+// mutable query item, marks the component as changed once it is written through
+pub struct Mut<'world, T> {
+    value: &'world mut T,
+    ticks: &'world mut ComponentTicks,
+    last_run: u64,
+    change_tick: u64,
+}
+
+impl<'world, T> Mut<'world, T> {
+    pub(crate) fn new(
+        value: &'world mut T,
+        ticks: &'world mut ComponentTicks,
+        last_run: u64,
+        change_tick: u64,
+    ) -> Self {
+        Self {
+            value,
+            ticks,
+            last_run,
+            change_tick,
+        }
+    }
+
+    // added since the last run of the current system
+    #[must_use]
+    pub fn is_added(&self) -> bool {
+        self.ticks.added > self.last_run
+    }
+
+    // changed since the last run of the current system, adding counts as a change
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.ticks.changed > self.last_run
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> &'world mut T {
+        self.ticks.changed = self.change_tick;
+        self.value
+    }
+
+    // for bookkeeping that must not show up as a change
+    #[must_use]
+    pub fn bypass_change_detection(self) -> &'world mut T {
+        self.value
+    }
+}
+
+impl<T> Deref for Mut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> DerefMut for Mut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.ticks.changed = self.change_tick;
+        self.value
+    }
+}
+
+// filters, only match entities whose T was added or changed since the last run
+pub struct Added<T>(PhantomData<T>);
+
+pub struct Changed<T>(PhantomData<T>);
+
+impl<T> QueryData for Added<T>
+where
+    T: Component,
+{
+    type Item<'world> = ();
+    type State = (*const ComponentStorage<T>, u64);
+
+    fn access(access: &mut Vec<(TypeId, bool)>) {
+        access.push((TypeId::of::<T>(), false));
+    }
+
+    fn init(component_manager: &mut ComponentManager) -> Option<Self::State> {
+        let storage = component_manager.storage::<T>().map(ptr::from_ref)?;
+
+        Some((storage, component_manager.last_run()))
+    }
+
+    fn driver_len(state: &Self::State) -> Option<usize> {
+        Some(unsafe { (*state.0).len() })
+    }
+
+    unsafe fn driver_entities<'world>(state: &Self::State) -> &'world [Entity] {
+        (*state.0).entities()
+    }
+
+    unsafe fn fetch<'world>(state: &Self::State, entity: Entity) -> Option<Self::Item<'world>> {
+        let ticks = (*state.0).get_ticks(entity)?;
+
+        (ticks.added > state.1).then_some(())
+    }
+}
+
+impl<T> QueryData for Changed<T>
+where
+    T: Component,
+{
+    type Item<'world> = ();
+    type State = (*const ComponentStorage<T>, u64);
+
+    fn access(access: &mut Vec<(TypeId, bool)>) {
+        access.push((TypeId::of::<T>(), false));
+    }
+
+    fn init(component_manager: &mut ComponentManager) -> Option<Self::State> {
+        let storage = component_manager.storage::<T>().map(ptr::from_ref)?;
+
+        Some((storage, component_manager.last_run()))
+    }
+
+    fn driver_len(state: &Self::State) -> Option<usize> {
+        Some(unsafe { (*state.0).len() })
+    }
+
+    unsafe fn driver_entities<'world>(state: &Self::State) -> &'world [Entity] {
+        (*state.0).entities()
+    }
+
+    unsafe fn fetch<'world>(state: &Self::State, entity: Entity) -> Option<Self::Item<'world>> {
+        let ticks = (*state.0).get_ticks(entity)?;
+
+        (ticks.changed > state.1).then_some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::super::{
+            entity::EntityProvider,
+            system::{Stage, System},
+            testing::{self, Entities, Health},
+            time::Time,
+            ECS,
+        },
+        Added, Changed,
+    };
+
+    // counts of added and changed health seen by each run of a system
+    #[derive(Default)]
+    struct Seen {
+        heal: Vec<(usize, usize)>,
+        watch: Vec<(usize, usize)>,
+    }
+
+    fn count(ecs: &mut ECS<()>) -> (usize, usize) {
+        (
+            ecs.query::<Added<Health>>().count(),
+            ecs.query::<Changed<Health>>().count(),
+        )
+    }
+
+    // raises health below 10 by 5, but only after looking at what changed
+    struct Heal;
+
+    impl System<()> for Heal {
+        fn run(&mut self, ecs: &mut ECS<()>, _provider: &dyn EntityProvider) {
+            let seen = count(ecs);
+            if let Some(recorded) = ecs.resource_mut::<Seen>() {
+                recorded.heal.push(seen);
+            }
+
+            for (_, mut health) in ecs.query::<&mut Health>() {
+                if health.0 < 10 {
+                    health.0 += 5;
+                }
+            }
+        }
+    }
+
+    struct Watch;
+
+    impl System<()> for Watch {
+        fn run(&mut self, ecs: &mut ECS<()>, _provider: &dyn EntityProvider) {
+            let seen = count(ecs);
+            if let Some(recorded) = ecs.resource_mut::<Seen>() {
+                recorded.watch.push(seen);
+            }
+        }
+    }
+
+    // marks every health as visited without changing it
+    struct Visit;
+
+    impl System<()> for Visit {
+        fn run(&mut self, ecs: &mut ECS<()>, _provider: &dyn EntityProvider) {
+            for (_, health) in ecs.query::<&mut Health>() {
+                let health = health.bypass_change_detection();
+                health.0 = health.0.max(1);
+            }
+        }
+    }
+
+    // exactly one step per update
+    fn stepped_ecs() -> ECS<()> {
+        let mut ecs = testing::ecs();
+        ecs.remove_resource::<Time>();
+        ecs.insert_resource(Seen::default());
+
+        ecs
+    }
+
+    #[test]
+    fn systems_see_every_change_once() {
+        let mut ecs = stepped_ecs();
+        ecs.add_system(Stage::Update, Heal);
+        ecs.add_system(Stage::PostUpdate, Watch);
+
+        let full = ecs.create_entity();
+        ecs.add_component(full, Health(10));
+        ecs.update(&Entities(Vec::new()));
+
+        let hurt = ecs.create_entity();
+        ecs.add_component(hurt, Health(3));
+        ecs.update(&Entities(Vec::new()));
+        ecs.update(&Entities(Vec::new()));
+        ecs.update(&Entities(Vec::new()));
+
+        let seen = ecs.resource::<Seen>().expect("Seen was removed!");
+        // heal never sees its own writes, watch sees them once
+        assert_eq!(seen.heal, [(1, 1), (1, 1), (0, 0), (0, 0)]);
+        assert_eq!(seen.watch, [(1, 1), (1, 1), (0, 1), (0, 0)]);
+        assert_eq!(ecs.get_component::<Health>(hurt), Some(&Health(13)));
+    }
+
+    #[test]
+    fn bypassed_writes_are_not_changes() {
+        let mut ecs = stepped_ecs();
+        ecs.add_system(Stage::Update, Visit);
+        ecs.add_system(Stage::PostUpdate, Watch);
+
+        let entity = ecs.create_entity();
+        ecs.add_component(entity, Health(0));
+        ecs.update(&Entities(Vec::new()));
+        ecs.update(&Entities(Vec::new()));
+
+        let seen = ecs.resource::<Seen>().expect("Seen was removed!");
+        assert_eq!(seen.watch, [(1, 1), (0, 0)]);
+        assert_eq!(ecs.get_component::<Health>(entity), Some(&Health(1)));
+    }
+}
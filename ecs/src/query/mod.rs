@@ -0,0 +1,356 @@
+mod change;
+
+use super::{
+    component::{Component, ComponentManager, ComponentStorage, DisabledComponent},
+    entity::Entity,
+};
+
+use std::{any::TypeId, borrow::Cow, collections::HashSet, marker::PhantomData, ptr};
+
+pub use change::{Added, Changed, Mut};
+
+// implemented for &T, &mut T, Option<_>, the change filters and tuples of those
+pub trait QueryData {
+    type Item<'world>;
+    type State;
+
+    // every accessed component with its mutability, used to reject aliasing queries
+    fn access(access: &mut Vec<(TypeId, bool)>);
+
+    // None if a required storage does not exist, the query is empty then
+    fn init(component_manager: &mut ComponentManager) -> Option<Self::State>;
+
+    // size of the smallest required storage, None if nothing is required
+    fn driver_len(state: &Self::State) -> Option<usize>;
+
+    /// # Safety
+    /// `state` must point to live storages created by `init`, which outlive 'world.
+    unsafe fn driver_entities<'world>(state: &Self::State) -> &'world [Entity];
+
+    /// # Safety
+    /// `state` must point to live storages created by `init` and no entity may be fetched twice
+    /// while a previously fetched item is still alive.
+    unsafe fn fetch<'world>(state: &Self::State, entity: Entity) -> Option<Self::Item<'world>>;
+}
+
+impl<T> QueryData for &T
+where
+    T: Component,
+{
+    type Item<'world> = &'world T;
+    type State = *const ComponentStorage<T>;
+
+    fn access(access: &mut Vec<(TypeId, bool)>) {
+        access.push((TypeId::of::<T>(), false));
+    }
+
+    fn init(component_manager: &mut ComponentManager) -> Option<Self::State> {
+        component_manager.storage::<T>().map(ptr::from_ref)
+    }
+
+    fn driver_len(state: &Self::State) -> Option<usize> {
+        Some(unsafe { (**state).len() })
+    }
+
+    unsafe fn driver_entities<'world>(state: &Self::State) -> &'world [Entity] {
+        (**state).entities()
+    }
+
+    unsafe fn fetch<'world>(state: &Self::State, entity: Entity) -> Option<Self::Item<'world>> {
+        (**state).get(entity)
+    }
+}
+
+impl<T> QueryData for &mut T
+where
+    T: Component,
+{
+    type Item<'world> = Mut<'world, T>;
+    // storage and the last run to compare change ticks against
+    type State = (*mut ComponentStorage<T>, u64);
+
+    fn access(access: &mut Vec<(TypeId, bool)>) {
+        access.push((TypeId::of::<T>(), true));
+    }
+
+    fn init(component_manager: &mut ComponentManager) -> Option<Self::State> {
+        let last_run = component_manager.last_run();
+        let storage = component_manager.storage_mut::<T>().map(ptr::from_mut)?;
+
+        Some((storage, last_run))
+    }
+
+    fn driver_len(state: &Self::State) -> Option<usize> {
+        Some(unsafe { (*state.0).len() })
+    }
+
+    unsafe fn driver_entities<'world>(state: &Self::State) -> &'world [Entity] {
+        (*state.0).entities()
+    }
+
+    unsafe fn fetch<'world>(state: &Self::State, entity: Entity) -> Option<Self::Item<'world>> {
+        let change_tick = (*state.0).change_tick();
+        let (value, ticks) = (*state.0).get_mut_with_ticks(entity)?;
+
+        Some(Mut::new(value, ticks, state.1, change_tick))
+    }
+}
+
+impl<Q> QueryData for Option<Q>
+where
+    Q: QueryData,
+{
+    type Item<'world> = Option<Q::Item<'world>>;
+    type State = Option<Q::State>;
+
+    fn access(access: &mut Vec<(TypeId, bool)>) {
+        Q::access(access);
+    }
+
+    fn init(component_manager: &mut ComponentManager) -> Option<Self::State> {
+        Some(Q::init(component_manager))
+    }
+
+    fn driver_len(_state: &Self::State) -> Option<usize> {
+        None
+    }
+
+    unsafe fn driver_entities<'world>(_state: &Self::State) -> &'world [Entity] {
+        &[]
+    }
+
+    unsafe fn fetch<'world>(state: &Self::State, entity: Entity) -> Option<Self::Item<'world>> {
+        Some(state.as_ref().and_then(|state| Q::fetch(state, entity)))
+    }
+}
+
+macro_rules! impl_query_data_for_tuple {
+    ($(($param:ident, $index:tt)),+) => {
+        impl<$($param),+> QueryData for ($($param,)+)
+        where
+            $($param: QueryData),+
+        {
+            type Item<'world> = ($($param::Item<'world>,)+);
+            type State = ($($param::State,)+);
+
+            fn access(access: &mut Vec<(TypeId, bool)>) {
+                $($param::access(access);)+
+            }
+
+            fn init(component_manager: &mut ComponentManager) -> Option<Self::State> {
+                Some(($($param::init(component_manager)?,)+))
+            }
+
+            fn driver_len(state: &Self::State) -> Option<usize> {
+                [$($param::driver_len(&state.$index)),+].into_iter().flatten().min()
+            }
+
+            unsafe fn driver_entities<'world>(state: &Self::State) -> &'world [Entity] {
+                let Some(smallest) = Self::driver_len(state) else {
+                    return &[];
+                };
+
+                $(
+                    if $param::driver_len(&state.$index) == Some(smallest) {
+                        return $param::driver_entities(&state.$index);
+                    }
+                )+
+
+                &[]
+            }
+
+            unsafe fn fetch<'world>(
+                state: &Self::State,
+                entity: Entity,
+            ) -> Option<Self::Item<'world>> {
+                Some(($($param::fetch(&state.$index, entity)?,)+))
+            }
+        }
+    };
+}
+
+impl_query_data_for_tuple!((A, 0));
+impl_query_data_for_tuple!((A, 0), (B, 1));
+impl_query_data_for_tuple!((A, 0), (B, 1), (C, 2));
+impl_query_data_for_tuple!((A, 0), (B, 1), (C, 2), (D, 3));
+impl_query_data_for_tuple!((A, 0), (B, 1), (C, 2), (D, 3), (F, 4));
+impl_query_data_for_tuple!((A, 0), (B, 1), (C, 2), (D, 3), (F, 4), (G, 5));
+
+pub struct Query<'world, Q>
+where
+    Q: QueryData,
+{
+    state: Option<Q::State>,
+    entities: Cow<'world, [Entity]>,
+    // pooled entities are skipped
+    disabled: Option<*const ComponentStorage<DisabledComponent>>,
+    next: usize,
+    // the query holds the component manager exclusively for as long as items are alive
+    world: PhantomData<&'world mut ComponentManager>,
+}
+
+impl<'world, Q> Query<'world, Q>
+where
+    Q: QueryData,
+{
+    pub(crate) fn new(
+        component_manager: &'world mut ComponentManager,
+        entities: Option<&[Entity]>,
+    ) -> Self {
+        assert_no_aliasing::<Q>();
+
+        let disabled = component_manager
+            .storage::<DisabledComponent>()
+            .filter(|disabled_storage| disabled_storage.len() > 0)
+            .map(ptr::from_ref);
+
+        let state = Q::init(component_manager);
+
+        let entities = match (&state, entities) {
+            (None, _) => Cow::Borrowed(&[][..]),
+            // every entity must be visited only once, otherwise mutable items would alias
+            (Some(_), Some(entities)) => {
+                let mut seen = HashSet::with_capacity(entities.len());
+                Cow::Owned(
+                    entities
+                        .iter()
+                        .filter(|entity| seen.insert(**entity))
+                        .copied()
+                        .collect(),
+                )
+            }
+            (Some(state), None) => {
+                assert!(
+                    Q::driver_len(state).is_some(),
+                    "Query needs at least one required component!"
+                );
+                Cow::Borrowed(unsafe { Q::driver_entities(state) })
+            }
+        };
+
+        Self {
+            state,
+            entities,
+            disabled,
+            next: 0,
+            world: PhantomData,
+        }
+    }
+}
+
+impl<'world, Q> Iterator for Query<'world, Q>
+where
+    Q: QueryData,
+{
+    type Item = (Entity, Q::Item<'world>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = self.state.as_ref()?;
+
+        while let Some(entity) = self.entities.get(self.next).copied() {
+            self.next += 1;
+
+            let is_disabled = self
+                .disabled
+                .is_some_and(|disabled| unsafe { (*disabled).get(entity).is_some() });
+            if is_disabled {
+                continue;
+            }
+
+            // entities are unique and the storages are borrowed for 'world, see Query::new
+            if let Some(item) = unsafe { Q::fetch(state, entity) } {
+                return Some((entity, item));
+            }
+        }
+
+        None
+    }
+}
+
+fn assert_no_aliasing<Q>()
+where
+    Q: QueryData,
+{
+    let mut access = Vec::with_capacity(4);
+    Q::access(&mut access);
+
+    for (index, (type_id, mutable)) in access.iter().enumerate() {
+        for (other_type_id, other_mutable) in &access[index + 1..] {
+            assert!(
+                type_id != other_type_id || !(*mutable || *other_mutable),
+                "Query accesses the same component mutably more than once!"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::{self, Armor, Entities, Health};
+
+    #[test]
+    fn queries_visit_entities_with_all_required_components() {
+        let mut ecs = testing::ecs();
+        let both = ecs.create_entity();
+        ecs.add_component(both, Health(10));
+        ecs.add_component(both, Armor(2));
+        let health_only = ecs.create_entity();
+        ecs.add_component(health_only, Health(5));
+
+        for (_, (mut health, armor)) in ecs.query::<(&mut Health, &Armor)>() {
+            health.0 += armor.0;
+        }
+
+        assert_eq!(ecs.get_component::<Health>(both), Some(&Health(12)));
+        assert_eq!(ecs.get_component::<Health>(health_only), Some(&Health(5)));
+
+        let optional: Vec<_> = ecs
+            .query::<(&Health, Option<&Armor>)>()
+            .map(|(entity, (_, armor))| (entity, armor.copied()))
+            .collect();
+        assert_eq!(optional.len(), 2);
+        assert!(optional.contains(&(both, Some(Armor(2)))));
+        assert!(optional.contains(&(health_only, None)));
+    }
+
+    #[test]
+    fn shared_access_to_the_same_component_is_allowed() {
+        let mut ecs = testing::ecs();
+        let entity = ecs.create_entity();
+        ecs.add_component(entity, Health(3));
+
+        assert_eq!(ecs.query::<(&Health, &Health)>().count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Query accesses the same component mutably more than once!")]
+    fn mutable_aliasing_is_rejected() {
+        let mut ecs = testing::ecs();
+        let entity = ecs.create_entity();
+        ecs.add_component(entity, Health(3));
+
+        let _ = ecs.query::<(&mut Health, &Health)>();
+    }
+
+    #[test]
+    #[should_panic(expected = "Query accesses the same component mutably more than once!")]
+    fn nested_mutable_aliasing_is_rejected() {
+        let mut ecs = testing::ecs();
+
+        let _ = ecs.query::<(&Armor, (Option<&mut Health>, &mut Health))>();
+    }
+
+    #[test]
+    fn duplicate_entities_are_visited_once() {
+        let mut ecs = testing::ecs();
+        let entity = ecs.create_entity();
+        ecs.add_component(entity, Health(1));
+
+        let entities = Entities(vec![entity, entity]);
+        for (_, mut health) in ecs.query_in::<&mut Health, _>(&entities) {
+            health.0 += 1;
+        }
+
+        assert_eq!(ecs.get_component::<Health>(entity), Some(&Health(2)));
+    }
+}
@@ -0,0 +1,54 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+// game wide singletons, at most one value per type
+#[derive(Default)]
+pub(crate) struct ResourceManager {
+    resources: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl ResourceManager {
+    pub fn insert<T>(&mut self, resource: T) -> Option<T>
+    where
+        T: 'static,
+    {
+        let replaced = self
+            .resources
+            .insert(TypeId::of::<T>(), Box::new(resource))?;
+
+        Some(
+            *replaced
+                .downcast()
+                .expect("Resource stored under the wrong type!"),
+        )
+    }
+
+    pub fn remove<T>(&mut self) -> Option<T>
+    where
+        T: 'static,
+    {
+        let removed = self.resources.remove(&TypeId::of::<T>())?;
+
+        Some(
+            *removed
+                .downcast()
+                .expect("Resource stored under the wrong type!"),
+        )
+    }
+
+    pub fn get<T>(&self) -> Option<&T>
+    where
+        T: 'static,
+    {
+        self.resources.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub fn get_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: 'static,
+    {
+        self.resources.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+}
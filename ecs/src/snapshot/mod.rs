@@ -0,0 +1,543 @@
+use super::{
+    component::{
+        Component, ComponentManager, NameComponent, ParentComponent, PhysicsComponent,
+        PositionComponent, TagComponent, TextComponent, VisualComponent,
+    },
+    entity::Entity,
+    resource::ResourceManager,
+    system::ResourceSystem,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+// components and resources of a set of entities, entities are referred to by their position
+#[derive(Default, Deserialize, Serialize)]
+pub struct Snapshot {
+    entity_count: usize,
+    roots: Vec<usize>,
+    player: Option<usize>,
+    components: BTreeMap<String, Vec<(usize, Value)>>,
+    resources: BTreeMap<String, Value>,
+}
+
+impl Snapshot {
+    #[must_use]
+    pub fn entity_count(&self) -> usize {
+        self.entity_count
+    }
+
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string_pretty(self).map_err(SnapshotError::Json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        serde_json::from_str(json).map_err(SnapshotError::Json)
+    }
+
+    pub fn write_to<P>(&self, path: P) -> Result<(), SnapshotError>
+    where
+        P: AsRef<Path>,
+    {
+        if let Some(directory) = path.as_ref().parent() {
+            fs::create_dir_all(directory).map_err(SnapshotError::Io)?;
+        }
+
+        fs::write(path, self.to_json()?).map_err(SnapshotError::Io)
+    }
+
+    pub fn read_from<P>(path: P) -> Result<Self, SnapshotError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_json(&fs::read_to_string(path).map_err(SnapshotError::Io)?)
+    }
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    UnknownTexture(String),
+    UnknownEntity(usize),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(formatter, "snapshot io failed: {error}"),
+            SnapshotError::Json(error) => write!(formatter, "snapshot is not valid: {error}"),
+            SnapshotError::UnknownTexture(name) => write!(formatter, "unknown texture: {name}"),
+            SnapshotError::UnknownEntity(id) => write!(formatter, "unknown entity id: {id}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+// entities that were restored, in the order of the provider that was saved
+pub struct LoadedSnapshot {
+    pub roots: Vec<Entity>,
+    pub player: Option<Entity>,
+}
+
+pub struct SaveContext<'save> {
+    entity_ids: HashMap<Entity, usize>,
+    resource_system: &'save ResourceSystem,
+}
+
+impl SaveContext<'_> {
+    #[must_use]
+    pub fn entity_id(&self, entity: Entity) -> Option<usize> {
+        self.entity_ids.get(&entity).copied()
+    }
+
+    #[must_use]
+    pub fn texture_name(&self, texture_index: usize) -> Option<&str> {
+        self.resource_system.get_texture_name(texture_index)
+    }
+}
+
+pub struct LoadContext<'load> {
+    entities: &'load [Entity],
+    resource_system: &'load ResourceSystem,
+}
+
+impl LoadContext<'_> {
+    pub fn entity(&self, id: usize) -> Result<Entity, SnapshotError> {
+        self.entities
+            .get(id)
+            .copied()
+            .ok_or(SnapshotError::UnknownEntity(id))
+    }
+
+    pub fn texture_index(&self, name: &str) -> Result<usize, SnapshotError> {
+        self.resource_system
+            .find_texture_index(name)
+            .ok_or_else(|| SnapshotError::UnknownTexture(name.to_owned()))
+    }
+}
+
+// for components that need more than plain serde, e.g. texture indices or entity references
+pub trait SavedComponent: Component + Sized {
+    type Saved: Serialize + DeserializeOwned;
+
+    // None skips the component, e.g. if it refers to an entity outside of the snapshot
+    fn save(&self, context: &SaveContext) -> Option<Self::Saved>;
+
+    fn load(saved: Self::Saved, context: &LoadContext) -> Result<Self, SnapshotError>;
+}
+
+type SaveFn = fn(&ComponentManager, &SaveContext) -> Result<Vec<(usize, Value)>, SnapshotError>;
+type LoadFn =
+    fn(&mut ComponentManager, &LoadContext, &[(usize, Value)]) -> Result<(), SnapshotError>;
+type SaveResourceFn = fn(&ResourceManager) -> Result<Option<Value>, SnapshotError>;
+// resources are only inserted once the whole snapshot was loaded
+type PendingResource = Box<dyn FnOnce(&mut ResourceManager)>;
+type LoadResourceFn = fn(&Value) -> Result<PendingResource, SnapshotError>;
+
+struct ComponentEntry {
+    key: String,
+    save: SaveFn,
+    load: LoadFn,
+}
+
+struct ResourceEntry {
+    key: String,
+    save: SaveResourceFn,
+    load: LoadResourceFn,
+}
+
+pub(crate) struct SnapshotRegistry {
+    components: Vec<ComponentEntry>,
+    resources: Vec<ResourceEntry>,
+}
+
+impl Default for SnapshotRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            components: Vec::with_capacity(10),
+            resources: Vec::new(),
+        };
+
+        registry.register::<PositionComponent>("position");
+        registry.register::<PhysicsComponent>("physics");
+        registry.register::<VisualComponent>("visual");
+        registry.register::<TextComponent>("text");
+        registry.register::<ParentComponent>("parent");
+        registry.register::<NameComponent>("name");
+        registry.register::<TagComponent>("tags");
+
+        registry
+    }
+}
+
+impl SnapshotRegistry {
+    pub fn register<T>(&mut self, key: &str)
+    where
+        T: SavedComponent,
+    {
+        self.insert_component(key, save_component::<T>, load_component::<T>);
+    }
+
+    pub fn register_serde<T>(&mut self, key: &str)
+    where
+        T: Component + Serialize + DeserializeOwned,
+    {
+        self.insert_component(key, save_serde::<T>, load_serde::<T>);
+    }
+
+    pub fn register_resource<T>(&mut self, key: &str)
+    where
+        T: Serialize + DeserializeOwned + 'static,
+    {
+        self.resources.retain(|entry| entry.key != key);
+        self.resources.push(ResourceEntry {
+            key: key.to_owned(),
+            save: save_resource::<T>,
+            load: load_resource::<T>,
+        });
+    }
+
+    fn insert_component(&mut self, key: &str, save: SaveFn, load: LoadFn) {
+        self.components.retain(|entry| entry.key != key);
+        self.components.push(ComponentEntry {
+            key: key.to_owned(),
+            save,
+            load,
+        });
+    }
+
+    pub fn save(
+        &self,
+        component_manager: &ComponentManager,
+        resource_manager: &ResourceManager,
+        resource_system: &ResourceSystem,
+        entities: &[Entity],
+        roots: &[Entity],
+        player: Option<Entity>,
+    ) -> Result<Snapshot, SnapshotError> {
+        let context = SaveContext {
+            entity_ids: entities
+                .iter()
+                .enumerate()
+                .map(|(id, entity)| (*entity, id))
+                .collect(),
+            resource_system,
+        };
+
+        let mut snapshot = Snapshot {
+            entity_count: entities.len(),
+            roots: roots
+                .iter()
+                .filter_map(|root| context.entity_id(*root))
+                .collect(),
+            player: player.and_then(|player| context.entity_id(player)),
+            ..Snapshot::default()
+        };
+
+        for entry in &self.components {
+            let saved = (entry.save)(component_manager, &context)?;
+            if !saved.is_empty() {
+                snapshot.components.insert(entry.key.clone(), saved);
+            }
+        }
+
+        for entry in &self.resources {
+            if let Some(saved) = (entry.save)(resource_manager)? {
+                snapshot.resources.insert(entry.key.clone(), saved);
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    // resources are left untouched if anything fails, components of the given entities are not,
+    // the caller has to clear them
+    pub fn load(
+        &self,
+        component_manager: &mut ComponentManager,
+        resource_manager: &mut ResourceManager,
+        resource_system: &ResourceSystem,
+        snapshot: &Snapshot,
+        entities: &[Entity],
+    ) -> Result<LoadedSnapshot, SnapshotError> {
+        let context = LoadContext {
+            entities,
+            resource_system,
+        };
+
+        let loaded = LoadedSnapshot {
+            roots: snapshot
+                .roots
+                .iter()
+                .map(|root| context.entity(*root))
+                .collect::<Result<_, _>>()?,
+            player: snapshot
+                .player
+                .map(|player| context.entity(player))
+                .transpose()?,
+        };
+
+        // keys that are not registered (anymore) are skipped
+        let resources = self
+            .resources
+            .iter()
+            .filter_map(|entry| snapshot.resources.get(&entry.key).map(entry.load))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for entry in &self.components {
+            if let Some(saved) = snapshot.components.get(&entry.key) {
+                (entry.load)(component_manager, &context, saved)?;
+            }
+        }
+
+        for insert in resources {
+            insert(resource_manager);
+        }
+
+        Ok(loaded)
+    }
+}
+
+fn save_component<T>(
+    component_manager: &ComponentManager,
+    context: &SaveContext,
+) -> Result<Vec<(usize, Value)>, SnapshotError>
+where
+    T: SavedComponent,
+{
+    let Some(storage) = component_manager.storage::<T>() else {
+        return Ok(Vec::new());
+    };
+
+    let mut saved = Vec::new();
+    for entity in storage.entities() {
+        let Some(id) = context.entity_id(*entity) else {
+            continue;
+        };
+
+        let Some(component) = storage
+            .get(*entity)
+            .and_then(|component| component.save(context))
+        else {
+            continue;
+        };
+
+        saved.push((
+            id,
+            serde_json::to_value(component).map_err(SnapshotError::Json)?,
+        ));
+    }
+
+    // storages are unordered, sorted output keeps saves comparable
+    saved.sort_by_key(|(id, _)| *id);
+    Ok(saved)
+}
+
+fn load_component<T>(
+    component_manager: &mut ComponentManager,
+    context: &LoadContext,
+    saved: &[(usize, Value)],
+) -> Result<(), SnapshotError>
+where
+    T: SavedComponent,
+{
+    for (id, value) in saved {
+        let entity = context.entity(*id)?;
+        let saved = T::Saved::deserialize(value).map_err(SnapshotError::Json)?;
+
+        component_manager
+            .register::<T>()
+            .add(entity, T::load(saved, context)?);
+    }
+
+    Ok(())
+}
+
+fn save_serde<T>(
+    component_manager: &ComponentManager,
+    context: &SaveContext,
+) -> Result<Vec<(usize, Value)>, SnapshotError>
+where
+    T: Component + Serialize,
+{
+    let Some(storage) = component_manager.storage::<T>() else {
+        return Ok(Vec::new());
+    };
+
+    let mut saved = Vec::new();
+    for entity in storage.entities() {
+        let (Some(id), Some(component)) = (context.entity_id(*entity), storage.get(*entity)) else {
+            continue;
+        };
+
+        saved.push((
+            id,
+            serde_json::to_value(component).map_err(SnapshotError::Json)?,
+        ));
+    }
+
+    saved.sort_by_key(|(id, _)| *id);
+    Ok(saved)
+}
+
+fn load_serde<T>(
+    component_manager: &mut ComponentManager,
+    context: &LoadContext,
+    saved: &[(usize, Value)],
+) -> Result<(), SnapshotError>
+where
+    T: Component + DeserializeOwned,
+{
+    for (id, value) in saved {
+        let entity = context.entity(*id)?;
+        let component = T::deserialize(value).map_err(SnapshotError::Json)?;
+
+        component_manager.register::<T>().add(entity, component);
+    }
+
+    Ok(())
+}
+
+fn save_resource<T>(resource_manager: &ResourceManager) -> Result<Option<Value>, SnapshotError>
+where
+    T: Serialize + 'static,
+{
+    resource_manager
+        .get::<T>()
+        .map(|resource| serde_json::to_value(resource).map_err(SnapshotError::Json))
+        .transpose()
+}
+
+fn load_resource<T>(saved: &Value) -> Result<PendingResource, SnapshotError>
+where
+    T: DeserializeOwned + 'static,
+{
+    let resource = T::deserialize(saved).map_err(SnapshotError::Json)?;
+
+    Ok(Box::new(move |resource_manager| {
+        resource_manager.insert(resource);
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            testing::{self, Entities, Health},
+            ECS,
+        },
+        Snapshot, SnapshotError,
+    };
+
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Score(u32);
+
+    fn registered_ecs() -> ECS<()> {
+        let mut ecs = testing::ecs();
+        ecs.register_saved_serde::<Health>("health");
+        ecs.register_saved_resource::<Score>("score");
+
+        ecs
+    }
+
+    fn saved_snapshot() -> Snapshot {
+        let mut ecs = registered_ecs();
+        let knight = ecs.create_entity();
+        ecs.add_component(knight, Health(10));
+        ecs.set_name(knight, "knight");
+        let squire = ecs.create_entity();
+        ecs.add_component(squire, Health(4));
+        ecs.insert_resource(Score(3));
+
+        ecs.save_snapshot(&Entities(vec![knight, squire]))
+            .expect("Failed to save snapshot!")
+    }
+
+    #[test]
+    fn snapshots_round_trip() {
+        let json = saved_snapshot()
+            .to_json()
+            .expect("Failed to serialize snapshot!");
+        let snapshot = Snapshot::from_json(&json).expect("Failed to parse snapshot!");
+
+        let mut ecs = registered_ecs();
+        let loaded = ecs
+            .load_snapshot(&snapshot)
+            .expect("Failed to load snapshot!");
+
+        assert_eq!(ecs.entity_count(), 2);
+        assert_eq!(loaded.roots.len(), 2);
+        assert_eq!(ecs.find_by_name("knight"), Some(loaded.roots[0]));
+        assert_eq!(
+            ecs.get_component::<Health>(loaded.roots[0]),
+            Some(&Health(10))
+        );
+        assert_eq!(
+            ecs.get_component::<Health>(loaded.roots[1]),
+            Some(&Health(4))
+        );
+        assert_eq!(ecs.resource::<Score>(), Some(&Score(3)));
+    }
+
+    #[test]
+    fn invalid_player_changes_nothing() {
+        let mut snapshot = saved_snapshot();
+        snapshot.player = Some(5);
+
+        let mut ecs = registered_ecs();
+        ecs.insert_resource(Score(1));
+
+        assert!(matches!(
+            ecs.load_snapshot(&snapshot),
+            Err(SnapshotError::UnknownEntity(5))
+        ));
+        assert_eq!(ecs.entity_count(), 0);
+        assert_eq!(ecs.resource::<Score>(), Some(&Score(1)));
+    }
+
+    #[test]
+    fn invalid_component_changes_nothing() {
+        let mut snapshot = saved_snapshot();
+        snapshot
+            .components
+            .insert("health".to_owned(), vec![(1, json!("full"))]);
+
+        let mut ecs = registered_ecs();
+        ecs.insert_resource(Score(1));
+
+        assert!(matches!(
+            ecs.load_snapshot(&snapshot),
+            Err(SnapshotError::Json(_))
+        ));
+        assert_eq!(ecs.entity_count(), 0);
+        assert_eq!(ecs.find_by_name("knight"), None);
+        assert_eq!(ecs.resource::<Score>(), Some(&Score(1)));
+    }
+
+    #[test]
+    fn invalid_resource_changes_nothing() {
+        let mut snapshot = saved_snapshot();
+        snapshot.resources.insert("score".to_owned(), json!(-1));
+
+        let mut ecs = registered_ecs();
+        ecs.insert_resource(Score(1));
+
+        assert!(matches!(
+            ecs.load_snapshot(&snapshot),
+            Err(SnapshotError::Json(_))
+        ));
+        assert_eq!(ecs.entity_count(), 0);
+        assert_eq!(ecs.resource::<Score>(), Some(&Score(1)));
+    }
+}
@@ -0,0 +1,21 @@
+use super::{
+    super::{component::VisualComponent, entity::EntityProvider, time::Time, ECS},
+    System,
+};
+
+// advances texture animations in simulated time, registered into the update stage by default
+pub struct AnimationSystem;
+
+impl<E> System<E> for AnimationSystem
+where
+    E: 'static,
+{
+    fn run(&mut self, ecs: &mut ECS<E>, provider: &dyn EntityProvider) {
+        let delta = ecs.resource::<Time>().map_or(0.0, Time::delta_seconds);
+
+        for (_, visual) in ecs.query_in::<&mut VisualComponent, _>(provider) {
+            // animation frames are no change worth reporting
+            visual.bypass_change_detection().update_animation(delta);
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use super::super::{
+    component::{
+        ChildrenComponent, ComponentManager, ParentComponent, PositionComponent, PreviousPosition,
+        TransformComponent,
+    },
+    entity::Entity,
+};
+
+use glam::{Mat4, Vec3};
+
+pub struct HierarchySystem;
+
+impl HierarchySystem {
+    pub fn get_parent(component_manager: &ComponentManager, entity: Entity) -> Option<Entity> {
+        Some(
+            component_manager
+                .storage::<ParentComponent>()?
+                .get(entity)?
+                .parent,
+        )
+    }
+
+    pub fn get_children(component_manager: &ComponentManager, entity: Entity) -> &[Entity] {
+        component_manager
+            .storage::<ChildrenComponent>()
+            .and_then(|children_storage| children_storage.get(entity))
+            .map_or(&[], |children| &children.children)
+    }
+
+    pub fn is_ancestor(
+        component_manager: &ComponentManager,
+        ancestor: Entity,
+        mut entity: Entity,
+    ) -> bool {
+        while let Some(parent) = Self::get_parent(component_manager, entity) {
+            if parent == ancestor {
+                return true;
+            }
+            entity = parent;
+        }
+
+        false
+    }
+
+    // children follow their parent, so providers only have to list the roots
+    pub fn with_descendants(
+        component_manager: &ComponentManager,
+        entities: &[Entity],
+    ) -> Vec<Entity> {
+        let mut all = Vec::with_capacity(entities.len());
+
+        for entity in entities {
+            all.push(*entity);
+            Self::push_descendants(component_manager, *entity, &mut all);
+        }
+
+        all
+    }
+
+    pub fn push_descendants(
+        component_manager: &ComponentManager,
+        entity: Entity,
+        descendants: &mut Vec<Entity>,
+    ) {
+        for child in Self::get_children(component_manager, entity) {
+            descendants.push(*child);
+            Self::push_descendants(component_manager, *child, descendants);
+        }
+    }
+
+    pub fn attach(component_manager: &mut ComponentManager, child: Entity, parent: Entity) {
+        component_manager
+            .register::<ParentComponent>()
+            .add(child, ParentComponent { parent });
+
+        let children_storage = component_manager.register::<ChildrenComponent>();
+        if let Some(children) = children_storage.get_mut(parent) {
+            children.children.push(child);
+        } else {
+            children_storage.add(
+                parent,
+                ChildrenComponent {
+                    children: vec![child],
+                },
+            );
+        }
+    }
+
+    pub fn detach(component_manager: &mut ComponentManager, child: Entity) {
+        let Some(parent) = component_manager
+            .storage_mut::<ParentComponent>()
+            .and_then(|parent_storage| parent_storage.remove(child))
+        else {
+            return;
+        };
+
+        let Some(children_storage) = component_manager.storage_mut::<ChildrenComponent>() else {
+            return;
+        };
+
+        let Some(children) = children_storage.get_mut(parent.parent) else {
+            return;
+        };
+
+        children.children.retain(|sibling| *sibling != child);
+        if children.children.is_empty() {
+            children_storage.remove(parent.parent);
+        }
+    }
+
+    // remembers where everything was before a simulation step
+    pub fn record_positions(component_manager: &mut ComponentManager) {
+        let Some(position_storage) = component_manager.storage::<PositionComponent>() else {
+            return;
+        };
+
+        let positions: Vec<(Entity, Vec3)> = position_storage
+            .entities()
+            .iter()
+            .filter_map(|entity| Some((*entity, position_storage.get(*entity)?.xyz)))
+            .collect();
+
+        let previous_storage = component_manager.register::<PreviousPosition>();
+        for (entity, xyz) in positions {
+            match previous_storage.get_mut(entity) {
+                Some(previous) => previous.xyz = xyz,
+                None => previous_storage.add(entity, PreviousPosition { xyz }),
+            }
+        }
+    }
+
+    // alpha blends between the previous and the current step, 1 uses the current positions
+    pub fn propagate_transforms(component_manager: &mut ComponentManager, alpha: f32) {
+        let mut transforms = Vec::new();
+
+        if let Some(position_storage) = component_manager.storage::<PositionComponent>() {
+            for entity in position_storage.entities() {
+                if Self::get_parent(component_manager, *entity).is_none() {
+                    collect_transforms(
+                        component_manager,
+                        *entity,
+                        Mat4::IDENTITY,
+                        alpha,
+                        &mut transforms,
+                    );
+                }
+            }
+        }
+
+        // roots without a position just group their children
+        if let Some(children_storage) = component_manager.storage::<ChildrenComponent>() {
+            for entity in children_storage.entities() {
+                let is_root = Self::get_parent(component_manager, *entity).is_none();
+                let has_position = component_manager
+                    .storage::<PositionComponent>()
+                    .is_some_and(|position_storage| position_storage.get(*entity).is_some());
+
+                if is_root && !has_position {
+                    collect_transforms(
+                        component_manager,
+                        *entity,
+                        Mat4::IDENTITY,
+                        alpha,
+                        &mut transforms,
+                    );
+                }
+            }
+        }
+
+        let transform_storage = component_manager.register::<TransformComponent>();
+        for (entity, world) in transforms {
+            let Some(current) = transform_storage.get(entity) else {
+                transform_storage.add(entity, TransformComponent { world });
+                continue;
+            };
+
+            // only moved entities count as changed
+            if current.world != world {
+                if let Some(transform) = transform_storage.get_mut(entity) {
+                    transform.world = world;
+                }
+            }
+        }
+    }
+}
+
+fn collect_transforms(
+    component_manager: &ComponentManager,
+    entity: Entity,
+    parent_world: Mat4,
+    alpha: f32,
+    transforms: &mut Vec<(Entity, Mat4)>,
+) {
+    let position = component_manager
+        .storage::<PositionComponent>()
+        .and_then(|position_storage| position_storage.get(entity));
+
+    let world = match position {
+        Some(position) => {
+            // entities without a previous position were created after the last step
+            let xyz = component_manager
+                .storage::<PreviousPosition>()
+                .and_then(|previous_storage| previous_storage.get(entity))
+                .map_or(position.xyz, |previous| {
+                    previous.xyz.lerp(position.xyz, alpha)
+                });
+
+            let world =
+                parent_world * Mat4::from_translation(xyz) * Mat4::from_scale(position.scale);
+            transforms.push((entity, world));
+            world
+        }
+        None => parent_world,
+    };
+
+    for child in HierarchySystem::get_children(component_manager, entity) {
+        collect_transforms(component_manager, *child, world, alpha, transforms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::super::{
+            component::{PositionComponent, TransformComponent},
+            entity::Entity,
+            testing, ECS,
+        },
+        HierarchySystem,
+    };
+
+    use glam::Vec3;
+
+    fn positioned(ecs: &mut ECS<()>, xyz: Vec3, scale: f32) -> Entity {
+        let entity = ecs.create_entity();
+        ecs.add_component(
+            entity,
+            PositionComponent {
+                xyz,
+                scale: Vec3::splat(scale),
+            },
+        );
+
+        entity
+    }
+
+    fn world_position(ecs: &mut ECS<()>, entity: Entity) -> Option<Vec3> {
+        HierarchySystem::propagate_transforms(&mut ecs.component_manager, 1.0);
+
+        ecs.get_component::<TransformComponent>(entity)
+            .map(|transform| transform.world.w_axis.truncate())
+    }
+
+    // root -> child -> grandchild
+    fn family(ecs: &mut ECS<()>) -> (Entity, Entity, Entity) {
+        let root = positioned(ecs, Vec3::new(1.0, 0.0, 0.0), 2.0);
+        let child = positioned(ecs, Vec3::new(1.0, 0.0, 0.0), 1.0);
+        let grandchild = positioned(ecs, Vec3::new(0.0, 1.0, 0.0), 1.0);
+        ecs.set_parent(child, root);
+        ecs.set_parent(grandchild, child);
+
+        (root, child, grandchild)
+    }
+
+    #[test]
+    fn positions_are_relative_to_the_parent() {
+        let mut ecs = testing::ecs();
+        let (root, child, grandchild) = family(&mut ecs);
+
+        assert_eq!(
+            world_position(&mut ecs, root),
+            Some(Vec3::new(1.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            world_position(&mut ecs, child),
+            Some(Vec3::new(3.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            world_position(&mut ecs, grandchild),
+            Some(Vec3::new(3.0, 2.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn reparenting_moves_the_child() {
+        let mut ecs = testing::ecs();
+        let (root, child, grandchild) = family(&mut ecs);
+        let other = positioned(&mut ecs, Vec3::new(0.0, 5.0, 0.0), 1.0);
+
+        // cycles are rejected
+        ecs.set_parent(root, grandchild);
+        assert_eq!(ecs.get_parent(root), None);
+
+        ecs.set_parent(grandchild, other);
+        assert_eq!(ecs.get_parent(grandchild), Some(other));
+        assert_eq!(ecs.get_children(other), [grandchild]);
+        assert!(ecs.get_children(child).is_empty());
+        assert_eq!(
+            world_position(&mut ecs, grandchild),
+            Some(Vec3::new(0.0, 6.0, 0.0))
+        );
+
+        ecs.remove_parent(grandchild);
+        assert_eq!(ecs.get_parent(grandchild), None);
+        assert!(ecs.get_children(other).is_empty());
+        assert_eq!(
+            world_position(&mut ecs, grandchild),
+            Some(Vec3::new(0.0, 1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn descendants_are_destroyed_with_their_parent() {
+        let mut ecs = testing::ecs();
+        let (root, child, grandchild) = family(&mut ecs);
+        ecs.set_name(grandchild, "hat");
+        ecs.add_tag(child, "worn");
+        let other = positioned(&mut ecs, Vec3::ZERO, 1.0);
+
+        ecs.destroy_entity(root);
+        assert!(!ecs.is_alive(root));
+        assert!(!ecs.is_alive(child));
+        assert!(!ecs.is_alive(grandchild));
+        assert!(ecs.is_alive(other));
+        assert_eq!(ecs.find_by_name("hat"), None);
+        assert_eq!(ecs.entities_with_tag("worn").count(), 0);
+        assert_eq!(ecs.entity_count(), 1);
+    }
+
+    #[test]
+    fn destroyed_children_leave_their_parent() {
+        let mut ecs = testing::ecs();
+        let (root, child, grandchild) = family(&mut ecs);
+
+        ecs.destroy_entity(child);
+
+        assert!(ecs.is_alive(root));
+        assert!(!ecs.is_alive(grandchild));
+        assert!(ecs.get_children(root).is_empty());
+    }
+}
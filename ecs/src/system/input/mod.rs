@@ -14,7 +14,11 @@ use winit::{
 
 pub use mouse::{MouseEvent, MouseHandler, MousePosition};
 
-use super::super::{component::ComponentManager, entity::Entity, ECS};
+use super::super::{
+    component::{ComponentManager, InputComponent},
+    entity::{Entity, EntityManager},
+    ECS,
+};
 
 pub struct InputSystem {
     cursor_positions: HashMap<DeviceId, Vec2>,
@@ -78,45 +82,68 @@ impl InputSystem {
     }
 
     pub fn set_next_entity_to_active<E>(
-        component_manager: &mut ComponentManager<E>,
+        component_manager: &mut ComponentManager,
+        entity_manager: &EntityManager,
         currently_active: Entity,
-    ) {
-        let Some(active_input) = component_manager.input_storage.get_mut(currently_active) else {
+    ) where
+        E: 'static,
+    {
+        let Some(input_storage) = component_manager.storage_mut::<InputComponent<E>>() else {
             return;
         };
 
-        let Some(next_entity) = active_input.next else {
+        let Some(next_entity) = input_storage
+            .get(currently_active)
+            .and_then(|active_input| active_input.next)
+        else {
             return;
         };
 
-        active_input.is_active = false;
-
-        let Some(next_input) = component_manager.input_storage.get_mut(next_entity) else {
+        // a stale link must not hand the focus to whatever reused the slot
+        if !entity_manager.is_alive(next_entity) || input_storage.get(next_entity).is_none() {
             return;
-        };
+        }
+
+        if let Some(active_input) = input_storage.get_mut(currently_active) {
+            active_input.is_active = false;
+        }
 
-        next_input.is_active = true;
+        if let Some(next_input) = input_storage.get_mut(next_entity) {
+            next_input.is_active = true;
+        }
     }
 
     pub fn set_previous_entity_to_active<E>(
-        component_manager: &mut ComponentManager<E>,
+        component_manager: &mut ComponentManager,
+        entity_manager: &EntityManager,
         currently_active: Entity,
-    ) {
-        let Some(active_input) = component_manager.input_storage.get_mut(currently_active) else {
+    ) where
+        E: 'static,
+    {
+        let Some(input_storage) = component_manager.storage_mut::<InputComponent<E>>() else {
             return;
         };
 
-        let Some(previous_entity) = active_input.previous else {
+        let Some(previous_entity) = input_storage
+            .get(currently_active)
+            .and_then(|active_input| active_input.previous)
+        else {
             return;
         };
 
-        active_input.is_active = false;
-
-        let Some(previous_input) = component_manager.input_storage.get_mut(previous_entity) else {
+        // a stale link must not hand the focus to whatever reused the slot
+        if !entity_manager.is_alive(previous_entity) || input_storage.get(previous_entity).is_none()
+        {
             return;
-        };
+        }
 
-        previous_input.is_active = true;
+        if let Some(active_input) = input_storage.get_mut(currently_active) {
+            active_input.is_active = false;
+        }
+
+        if let Some(previous_input) = input_storage.get_mut(previous_entity) {
+            previous_input.is_active = true;
+        }
     }
 }
 
@@ -1,10 +1,10 @@
 use super::super::super::{
-    component::{ComponentManager, PositionComponent, Quad},
+    component::{ComponentManager, PositionComponent, Quad, TransformComponent},
     entity::Entity,
     system::InputSystem,
 };
 
-use glam::{Vec2, Vec3, Vec3Swizzles};
+use glam::{Mat4, Vec2, Vec3, Vec3Swizzles};
 use winit::event::DeviceId;
 
 #[derive(Eq, Hash, PartialEq)]
@@ -28,9 +28,9 @@ pub trait MouseHandler {
 
     fn handle_released(&mut self, mouse_button: winit::event::MouseButton, device_id: DeviceId);
 
-    fn entity_was_clicked<E>(
+    fn entity_was_clicked(
         &self,
-        component_manager: &ComponentManager<E>,
+        component_manager: &ComponentManager,
         position: &MousePosition,
         entity: Entity,
     ) -> bool;
@@ -78,25 +78,36 @@ impl MouseHandler for InputSystem {
         });
     }
 
-    fn entity_was_clicked<E>(
+    fn entity_was_clicked(
         &self,
-        component_manager: &ComponentManager<E>,
+        component_manager: &ComponentManager,
         position: &MousePosition,
         entity: Entity,
     ) -> bool {
-        let Some(component_position) = component_manager.position_storage.get(entity) else {
-            return false;
+        let transform = component_manager
+            .storage::<TransformComponent>()
+            .and_then(|transform_storage| transform_storage.get(entity));
+
+        // transforms are missing until the first propagation after spawning
+        let model_matrix = if let Some(transform) = transform {
+            transform.world
+        } else {
+            let Some(component_position) = component_manager
+                .storage::<PositionComponent>()
+                .and_then(|position_storage| position_storage.get(entity))
+            else {
+                return false;
+            };
+
+            component_position.get_model_matrix()
         };
 
-        component_was_clicked(component_position, position)
+        component_was_clicked(model_matrix, position)
     }
 }
 
-fn component_was_clicked(
-    component_position: &PositionComponent,
-    mouse_position: &MousePosition,
-) -> bool {
-    let current_geometry = quad_from_model(component_position);
+fn component_was_clicked(model_matrix: Mat4, mouse_position: &MousePosition) -> bool {
+    let current_geometry = quad_from_model(model_matrix);
 
     let Some(release_position) = mouse_position.released else {
         return false;
@@ -106,8 +117,7 @@ fn component_was_clicked(
         && current_geometry.position_is_inside(release_position)
 }
 
-fn quad_from_model(position: &PositionComponent) -> Quad {
-    let model_matrix = position.get_model_matrix();
+fn quad_from_model(model_matrix: Mat4) -> Quad {
     let mut geometry = Quad::new();
 
     let bottom_left = model_matrix.transform_point3(Vec3 {
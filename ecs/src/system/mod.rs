@@ -1,24 +1,48 @@
+mod animation;
+mod hierarchy;
 mod input;
+mod movement;
 mod render;
 mod resource;
+mod schedule;
 
 use rendering::Renderer;
-use resource::ResourceSystem;
+pub use resource::ResourceError;
+pub(crate) use resource::{ResourceSystem, NOT_FOUND_INDEX};
 
+pub use animation::AnimationSystem;
+pub use hierarchy::HierarchySystem;
 pub use input::{InputHandler, InputSystem, MouseEvent, MouseHandler, MousePosition};
-pub use render::RenderSystem;
+pub use movement::MovementSystem;
+pub use render::{RenderSystem, RenderTimings, ScreenEffect};
+pub(crate) use schedule::Schedule;
+pub use schedule::{Stage, System, SystemConfig};
 
-pub(crate) struct SystemManager {
+pub(crate) struct SystemManager<E>
+where
+    E: 'static,
+{
     pub resource_system: ResourceSystem,
     pub input_system: InputSystem,
+    pub render_system: RenderSystem,
+    pub schedule: Schedule<E>,
 }
 
-impl SystemManager {
-    pub fn create(texture_path: &str, font_path: &str) -> Self {
-        Self {
-            resource_system: ResourceSystem::create(texture_path, font_path),
+impl<E> SystemManager<E>
+where
+    E: 'static,
+{
+    pub fn create(texture_path: &str, font_path: &str) -> Result<Self, ResourceError> {
+        let mut schedule = Schedule::default();
+        schedule.add_system(Stage::Update, MovementSystem);
+        schedule.add_system(Stage::Update, AnimationSystem);
+
+        Ok(Self {
+            resource_system: ResourceSystem::create(texture_path, font_path)?,
             input_system: InputSystem::default(),
-        }
+            render_system: RenderSystem::default(),
+            schedule,
+        })
     }
 
     pub fn initialize<R>(&mut self, renderer: &R)
@@ -0,0 +1,27 @@
+use super::{
+    super::{
+        component::{PhysicsComponent, PositionComponent},
+        entity::EntityProvider,
+        time::Time,
+        ECS,
+    },
+    System,
+};
+
+// applies physics velocities in units per second, registered into the update stage by default
+pub struct MovementSystem;
+
+impl<E> System<E> for MovementSystem
+where
+    E: 'static,
+{
+    fn run(&mut self, ecs: &mut ECS<E>, provider: &dyn EntityProvider) {
+        let delta = ecs.resource::<Time>().map_or(0.0, Time::delta_seconds);
+
+        for (_, (mut position, physics)) in
+            ecs.query_in::<(&mut PositionComponent, &PhysicsComponent), _>(provider)
+        {
+            position.xyz += physics.velocity * delta;
+        }
+    }
+}
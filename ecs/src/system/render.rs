@@ -1,99 +1,147 @@
 use super::{
     super::{
         component::{
-            composition::{RenderTarget, TextWithPosition, VisualWithPosition},
-            ComponentStorage, Layer, PositionComponent, TextComponent, VisualComponent,
+            composition::{RenderTarget, TextWithTransform, VisualWithTransform},
+            ComponentManager, Layer, TextComponent, TransformComponent, VisualComponent,
         },
         entity::{Entity, EntityProvider},
+        query::Query,
     },
-    ResourceSystem,
+    HierarchySystem, ResourceSystem,
 };
 
-use glam::Mat4;
+use glam::{Mat4, Vec2, Vec3};
 use rendering::{ImageView, Renderer, MVP};
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    time::{Duration, Instant},
+};
+
+type RenderQuery<'components> = (
+    Option<&'components mut VisualComponent>,
+    Option<&'components mut TextComponent>,
+    &'components TransformComponent,
+);
 
-pub struct RenderSystem;
+// cpu time spent on a frame, prep is everything before the renderer is handed the frame
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RenderTimings {
+    pub prep: Duration,
+    pub submit: Duration,
+}
+
+// applied to everything that is drawn, e.g. by scene transitions
+#[derive(Clone, Copy, Debug)]
+pub struct ScreenEffect {
+    pub opacity: f32,
+    // in normalized device coordinates, a full screen is 2.0 wide
+    pub offset: Vec2,
+}
+
+impl Default for ScreenEffect {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct RenderSystem {
+    // change tick of the previous frame, text is re-rasterized if it changed since
+    last_run: u64,
+}
 
 impl RenderSystem {
-    pub fn draw<'components, P, R>(
+    pub fn draw<P, R>(
+        &mut self,
         renderer: &mut R,
         provider: &P,
-        visual_storage: &'components mut ComponentStorage<VisualComponent>,
-        text_storage: &'components mut ComponentStorage<TextComponent>,
-        position_storage: &'components ComponentStorage<PositionComponent>,
+        component_manager: &mut ComponentManager,
         resource_system: &mut ResourceSystem,
-    ) where
+        screen_effect: ScreenEffect,
+    ) -> Duration
+    where
         P: EntityProvider,
         R: Renderer,
     {
-        let mut render_targets: Vec<RenderTarget> = get_render_targets(
-            provider.get_entities(),
-            visual_storage,
-            text_storage,
-            position_storage,
-        );
+        self.last_run = component_manager.begin_run(self.last_run);
+
+        let player_position = get_player_position(provider.get_player(), component_manager);
+
+        let entities =
+            HierarchySystem::with_descendants(component_manager, provider.get_entities());
+        let mut render_targets: Vec<RenderTarget> = get_render_targets(Query::<RenderQuery>::new(
+            component_manager,
+            Some(&entities),
+        ));
 
         let textures = get_render_resources(renderer, &mut render_targets, resource_system);
-        let positions =
-            get_render_positions(&mut render_targets, provider.get_player(), position_storage);
+        let positions = get_render_positions(&render_targets, player_position, screen_effect);
 
+        let submit_start = Instant::now();
         renderer.draw(&textures, &positions);
+        let submit_time = submit_start.elapsed();
+
+        component_manager.end_run();
+
+        submit_time
     }
 }
 
+fn get_player_position(
+    player: Option<Entity>,
+    component_manager: &ComponentManager,
+) -> Option<Vec3> {
+    let transform = component_manager
+        .storage::<TransformComponent>()?
+        .get(player?)?;
+
+    Some(transform.world.w_axis.truncate())
+}
+
 fn get_render_targets<'components>(
-    entities: &[Entity],
-    visual_storage: &'components mut ComponentStorage<VisualComponent>,
-    text_storage: &'components mut ComponentStorage<TextComponent>,
-    position_storage: &'components ComponentStorage<PositionComponent>,
+    query: Query<'components, RenderQuery<'components>>,
 ) -> Vec<RenderTarget<'components>> {
-    let mut render_targets: Vec<RenderTarget> = Vec::with_capacity(entities.len());
-
-    // collect visual entities
-    for (entity, visual) in visual_storage.iter_mut() {
-        // only render currently active entities
-        if !entities.contains(&entity) {
-            continue;
-        }
+    let mut visual_targets: Vec<RenderTarget> = Vec::new();
+    let mut text_targets: Vec<RenderTarget> = Vec::new();
 
+    for (_, (visual, text, transform)) in query {
         // skip invisible
-        if !visual.should_render() {
-            continue;
-        }
-
-        let Some(position) = position_storage.get(entity) else {
-            continue;
-        };
-
-        // update textures of all animated components
-        visual.update_animation();
-
-        render_targets.push(RenderTarget::Visual(VisualWithPosition {
-            visual,
-            position,
-        }));
-    }
+        if let Some(visual) = visual.filter(|visual| visual.should_render()) {
+            // rendering does not change the visual
+            let visual = visual.bypass_change_detection();
 
-    // collect text entities
-    for (entity, text) in text_storage.iter_mut() {
-        // only render currently active entities
-        if !entities.contains(&entity) {
-            continue;
+            visual_targets.push(RenderTarget::Visual(VisualWithTransform {
+                visual,
+                transform,
+            }));
         }
 
-        let Some(position) = position_storage.get(entity) else {
-            continue;
-        };
+        if let Some(text) = text {
+            let changed = text.is_changed();
 
-        render_targets.push(RenderTarget::Text(TextWithPosition { text, position }));
+            text_targets.push(RenderTarget::Text(TextWithTransform {
+                // storing the bitmap is not a change of the text
+                text: text.bypass_change_detection(),
+                changed,
+                transform,
+            }));
+        }
     }
 
+    // text is drawn on top of visuals sharing its layer and z
+    let mut render_targets = visual_targets;
+    render_targets.append(&mut text_targets);
+
     // sort all by layer and by individual z inside layers
     render_targets.sort_by(|a, b| {
         let layer_ordering = a.get_layer().value().cmp(&b.get_layer().value());
         if layer_ordering == Ordering::Equal {
-            a.get_position().xyz.z.total_cmp(&b.get_position().xyz.z)
+            a.get_transform()
+                .get_z()
+                .total_cmp(&b.get_transform().get_z())
         } else {
             layer_ordering
         }
@@ -114,48 +162,47 @@ where
         .iter_mut()
         .map(|target| match target {
             RenderTarget::Visual(v) => resource_system.get_texture(v.visual.get_current_texture()),
-            RenderTarget::Text(t) => resource_system.get_bitmap(renderer, t.text),
+            RenderTarget::Text(t) => resource_system.get_bitmap(renderer, t.text, t.changed),
         })
         .collect()
 }
 
 fn get_render_positions(
-    render_targets: &mut [RenderTarget],
-    player: Option<Entity>,
-    position_storage: &ComponentStorage<PositionComponent>,
+    render_targets: &[RenderTarget],
+    player_position: Option<Vec3>,
+    screen_effect: ScreenEffect,
 ) -> Vec<MVP> {
-    let player_position: Option<&PositionComponent> = match player {
-        Some(player_entity) => position_storage.get(player_entity),
-        None => None,
-    };
-
     let view_matrix = match player_position {
-        Some(pos) => Mat4::from_translation(-pos.xyz),
+        Some(xyz) => Mat4::from_translation(-xyz),
         // no player => no camera movement
         None => Mat4::IDENTITY,
     };
+    // the offset is applied after projecting, so it moves the picture and not the camera
+    let projection = Mat4::from_translation(screen_effect.offset.extend(0.0)) * get_projection();
 
     render_targets
         .iter()
         .map(|target| match target {
-            RenderTarget::Visual(visual_with_position) => {
+            RenderTarget::Visual(visual_with_transform) => {
                 // even if there is a player, interface should not move!
-                let view = match visual_with_position.visual.layer {
+                let view = match visual_with_transform.visual.layer {
                     Layer::Interface => Mat4::IDENTITY,
                     Layer::Game | Layer::Background => view_matrix,
                 };
-                MVP {
-                    model: visual_with_position.position.get_model_matrix(),
+                MVP::new(
+                    visual_with_transform.transform.world,
                     view,
-                    projection: get_projection(),
-                }
+                    projection,
+                    screen_effect.opacity,
+                )
             }
 
-            RenderTarget::Text(text) => MVP {
-                model: text.position.get_model_matrix(),
-                view: Mat4::IDENTITY,
-                projection: get_projection(),
-            },
+            RenderTarget::Text(text) => MVP::new(
+                text.transform.world,
+                Mat4::IDENTITY,
+                projection,
+                screen_effect.opacity,
+            ),
         })
         .collect()
 }
@@ -163,3 +210,65 @@ fn get_render_positions(
 fn get_projection() -> Mat4 {
     Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, -1.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::{
+        component::{PositionComponent, TextComponent, TextContent},
+        testing::{self, Entities, FakeRenderer},
+    };
+
+    use glam::Vec3;
+
+    fn content(text: &str) -> TextContent {
+        TextContent {
+            text: text.to_owned(),
+            font: "none".to_owned(),
+            font_size: 20.0,
+        }
+    }
+
+    #[test]
+    fn changed_text_is_rasterized_again() {
+        let mut ecs = testing::ecs();
+        let mut renderer = FakeRenderer::default();
+        ecs.initialize(&renderer);
+        let textures = renderer.created.get();
+
+        let label = ecs.create_entity();
+        ecs.add_component(
+            label,
+            PositionComponent {
+                xyz: Vec3::ZERO,
+                scale: Vec3::ONE,
+            },
+        );
+        ecs.add_component(label, TextComponent::create(content("0")));
+        let entities = Entities(vec![label]);
+
+        ecs.render(&mut renderer, &entities);
+        ecs.render(&mut renderer, &entities);
+        assert_eq!(renderer.created.get(), textures + 1);
+        assert!(renderer.retired.is_empty());
+
+        // writing the same content is a change, but not one that needs a new bitmap
+        if let Some(text) = ecs.get_component_mut::<TextComponent>(label) {
+            text.content = content("0");
+        }
+        ecs.render(&mut renderer, &entities);
+        assert_eq!(renderer.created.get(), textures + 1);
+
+        if let Some(text) = ecs.get_component_mut::<TextComponent>(label) {
+            text.content.text = "100".to_owned();
+        }
+        ecs.render(&mut renderer, &entities);
+        assert_eq!(renderer.created.get(), textures + 2);
+        assert_eq!(renderer.retired.len(), 1);
+
+        // the bitmap of a removed text is retired on the next frame
+        ecs.remove_component::<TextComponent>(label);
+        ecs.render(&mut renderer, &entities);
+        assert_eq!(renderer.retired.len(), 2);
+        assert_eq!(renderer.drawn, [1, 1, 1, 1, 0]);
+    }
+}
@@ -1,4 +1,4 @@
-use super::ResourceSystem;
+use super::{ResourceError, ResourceSystem};
 
 use ab_glyph::FontVec;
 use std::{
@@ -8,21 +8,21 @@ use std::{
     path::{Path, PathBuf},
 };
 
-fn read_font_file(file_path: &Path) -> Option<FontVec> {
-    let path = file_path.to_str()?;
-    let font_data = read_bytes_from_file(path).expect(&("Failed to read: ".to_string() + path));
-    match FontVec::try_from_vec(font_data) {
-        Ok(font) => Some(font),
-        Err(e) => {
-            println!("Invalid font data from {file_path:?}: {e}");
-            None
-        }
-    }
+fn read_font_file(file_path: &Path) -> std::result::Result<FontVec, ResourceError> {
+    let font_data = read_bytes_from_file(file_path)
+        .map_err(|error| ResourceError::Io(file_path.to_owned(), error))?;
+
+    FontVec::try_from_vec(font_data)
+        .map_err(|error| ResourceError::Font(file_path.to_owned(), error))
 }
 
 impl ResourceSystem {
+    // fonts that can not be read are left out, texts fall back to another one
     pub fn create_font_map(&self) -> HashMap<String, FontVec> {
-        let font_files = self.gather_font_files();
+        let font_files = self.gather_font_files().unwrap_or_else(|error| {
+            log::error!("{error}, texts can not be drawn");
+            Vec::new()
+        });
         let mut font_map = HashMap::with_capacity(font_files.len());
 
         for font_file in font_files {
@@ -30,8 +30,12 @@ impl ResourceSystem {
                 continue;
             };
 
-            let Some(font) = read_font_file(&font_file) else {
-                continue;
+            let font = match read_font_file(&font_file) {
+                Ok(font) => font,
+                Err(error) => {
+                    log::warn!("{error}");
+                    continue;
+                }
             };
 
             font_map.insert(file_name.to_string(), font);
@@ -40,11 +44,11 @@ impl ResourceSystem {
         font_map
     }
 
-    fn gather_font_files(&self) -> Vec<PathBuf> {
+    fn gather_font_files(&self) -> std::result::Result<Vec<PathBuf>, ResourceError> {
         let mut font_files = Vec::with_capacity(2);
 
         for entry in read_dir(&self.font_base_path)
-            .expect("Failed to read font base path!")
+            .map_err(|error| ResourceError::Io(self.font_base_path.clone(), error))?
             .flatten()
         {
             let path = entry.path();
@@ -55,11 +59,11 @@ impl ResourceSystem {
             }
         }
 
-        font_files
+        Ok(font_files)
     }
 }
 
-fn read_bytes_from_file(path: &str) -> Result<Vec<u8>> {
+fn read_bytes_from_file(path: &Path) -> Result<Vec<u8>> {
     let mut file = File::open(path)?;
     let mut buffer = Vec::new();
     file.read_to_end(&mut buffer)?;
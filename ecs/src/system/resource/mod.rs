@@ -2,14 +2,64 @@ mod font;
 mod text;
 mod texture;
 
-use ab_glyph::FontVec;
-use image::DynamicImage;
+use ab_glyph::{FontVec, InvalidFont};
+use image::{DynamicImage, ImageError};
 use rendering::{ImageData, ImageView, Renderer};
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    io,
+    path::PathBuf,
+};
 use texture::TextureTable;
+pub(crate) use texture::NOT_FOUND_INDEX;
 
 use super::super::component::TextComponent;
 
+#[derive(Debug)]
+pub enum ResourceError {
+    Io(PathBuf, io::Error),
+    Json(PathBuf, serde_json::Error),
+    Image(PathBuf, ImageError),
+    Font(PathBuf, InvalidFont),
+    UnknownTexture(String),
+    UnknownFont(String),
+    // e.g. empty text or only spaces
+    NoOutlines(String),
+}
+
+impl Display for ResourceError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            ResourceError::Io(path, error) => {
+                write!(formatter, "failed to read {}: {error}", path.display())
+            }
+            ResourceError::Json(path, error) => {
+                write!(formatter, "{} is not valid: {error}", path.display())
+            }
+            ResourceError::Image(path, error) => {
+                write!(
+                    formatter,
+                    "failed to open image {}: {error}",
+                    path.display()
+                )
+            }
+            ResourceError::Font(path, error) => {
+                write!(
+                    formatter,
+                    "invalid font data in {}: {error}",
+                    path.display()
+                )
+            }
+            ResourceError::UnknownTexture(name) => write!(formatter, "unknown texture: {name}"),
+            ResourceError::UnknownFont(name) => write!(formatter, "unknown font: {name}"),
+            ResourceError::NoOutlines(text) => write!(formatter, "nothing to draw in {text:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ResourceError {}
+
 pub(crate) struct ResourceSystem {
     font_base_path: PathBuf,
     images: Vec<DynamicImage>,
@@ -19,20 +69,20 @@ pub(crate) struct ResourceSystem {
 }
 
 impl ResourceSystem {
-    pub fn create(texture_path: &str, font_path: &str) -> Self {
+    pub fn create(texture_path: &str, font_path: &str) -> Result<Self, ResourceError> {
         let font_base_path = PathBuf::from(font_path);
 
-        let texture_table = TextureTable::from_json(texture_path);
+        let texture_table = TextureTable::from_json(texture_path)?;
         let (images, texture_indices) = texture_table.load_images();
         let textures = Vec::with_capacity(images.len());
 
-        Self {
+        Ok(Self {
             font_base_path,
             images,
             fonts: HashMap::with_capacity(5),
             textures,
             texture_indices,
-        }
+        })
     }
 
     pub fn initialize<R>(&mut self, renderer: &R)
@@ -51,30 +101,62 @@ impl ResourceSystem {
         self.images.len() as u32
     }
 
-    pub fn get_texture_index(&self, key: &str) -> usize {
-        *self
-            .texture_indices
-            .get(key)
-            .expect(&("Failed to get texture index: ".to_string() + key))
+    pub fn get_texture_index(&self, key: &str) -> Result<usize, ResourceError> {
+        self.find_texture_index(key)
+            .ok_or_else(|| ResourceError::UnknownTexture(key.to_owned()))
     }
 
+    pub fn find_texture_index(&self, key: &str) -> Option<usize> {
+        self.texture_indices.get(key).copied()
+    }
+
+    pub fn get_texture_name(&self, texture_index: usize) -> Option<&str> {
+        self.texture_indices
+            .iter()
+            .find(|(_, index)| **index == texture_index)
+            .map(|(name, _)| name.as_str())
+    }
+
+    // indices that are out of range show the 404 texture
     pub fn get_texture(&self, texture_index: usize) -> ImageView {
         self.textures
             .get(texture_index)
-            .expect(&("Failed to get texture: ".to_string() + &texture_index.to_string()))
+            .or_else(|| self.textures.get(NOT_FOUND_INDEX))
+            .expect("Textures were not initialized before rendering!")
             .get_view()
     }
 
-    fn get_font(&self, font: &str) -> &FontVec {
+    fn get_font(&self, font: &str) -> Result<&FontVec, ResourceError> {
         self.fonts
             .get(font)
-            .expect(&("Failed to get font: ".to_string() + font))
+            .ok_or_else(|| ResourceError::UnknownFont(font.to_owned()))
+    }
+
+    // the first font by name, so the fallback does not change between starts
+    fn default_font(&self) -> Option<&FontVec> {
+        self.fonts
+            .iter()
+            .min_by_key(|(name, _)| name.as_str())
+            .map(|(_, font)| font)
     }
 
-    pub fn get_bitmap<R>(&mut self, renderer: &R, component: &mut TextComponent) -> ImageView
+    pub fn get_bitmap<R>(
+        &mut self,
+        renderer: &mut R,
+        component: &mut TextComponent,
+        changed: bool,
+    ) -> ImageView
     where
         R: Renderer,
     {
+        // only content, font and size end up in the bitmap
+        if changed && component.rasterized.as_ref() != Some(&component.content) {
+            if let Some(outdated) = component.bitmap.take() {
+                // might still be used by frames in flight
+                renderer.destroy_image_data(outdated);
+            }
+        }
+
         let Some(bitmap) = &component.bitmap else {
             return self.create_bitmap(renderer, component);
         };
@@ -86,12 +168,23 @@ impl ResourceSystem {
     where
         R: Renderer,
     {
-        let image = self.text_to_image(&component.content);
+        let image = self
+            .text_to_image(&component.content)
+            .unwrap_or_else(|error| {
+                // blank labels are fine, anything else is reported
+                if let ResourceError::NoOutlines(_) = error {
+                    log::debug!("{error}");
+                } else {
+                    log::warn!("{error}, drawing nothing");
+                }
+                text::empty_bitmap()
+            });
 
         let bitmap = renderer.create_image_data(image);
         let view = bitmap.get_view();
 
         component.bitmap = Some(bitmap);
+        component.rasterized = Some(component.content.clone());
 
         view
     }
@@ -1,22 +1,40 @@
-use super::{super::super::component::TextContent, ResourceSystem};
+use super::{super::super::component::TextContent, ResourceError, ResourceSystem};
 
 use ab_glyph::{Font, FontVec, Glyph, OutlinedGlyph, Point, PxScale, Rect, ScaleFont};
 use image::{DynamicImage, ImageBuffer, Rgba};
 
 impl ResourceSystem {
-    pub fn text_to_image(&self, content: &TextContent) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    pub fn text_to_image(
+        &self,
+        content: &TextContent,
+    ) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, ResourceError> {
         let scale = PxScale::from(content.font_size);
-        let font = self.get_font(&content.font);
+        let font = match self.get_font(&content.font) {
+            Ok(font) => font,
+            Err(error) => {
+                let Some(font) = self.default_font() else {
+                    return Err(error);
+                };
+                log::warn!("{error}, using the default font");
+                font
+            }
+        };
         let scaled_font = font.as_scaled(scale);
 
         let glyphs = gather_glyphs(scaled_font, &content.text);
 
-        let (outlined, px_bounds) = get_glyph_outlines(glyphs, font);
+        let (outlined, px_bounds) = get_glyph_outlines(glyphs, font)
+            .ok_or_else(|| ResourceError::NoOutlines(content.text.clone()))?;
 
-        create_image_from_gylphs(outlined, px_bounds)
+        Ok(create_image_from_gylphs(outlined, px_bounds))
     }
 }
 
+// a single transparent pixel, for texts that have nothing to draw
+pub fn empty_bitmap() -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    ImageBuffer::from_pixel(1, 1, Rgba([0, 0, 0, 0]))
+}
+
 fn gather_glyphs<F, SF>(font: SF, text: &str) -> Vec<Glyph>
 where
     F: Font,
@@ -50,12 +68,13 @@ where
     glyphs
 }
 
-fn get_glyph_outlines(glyphs: Vec<Glyph>, font: &FontVec) -> (Vec<OutlinedGlyph>, Rect) {
+// nothing if no glyph has an outline, e.g. for spaces
+fn get_glyph_outlines(glyphs: Vec<Glyph>, font: &FontVec) -> Option<(Vec<OutlinedGlyph>, Rect)> {
     let outlined: Vec<OutlinedGlyph> = glyphs
         .into_iter()
         .filter_map(|glyph| font.outline_glyph(glyph))
         .collect();
-    let Some(px_bounds) = outlined
+    let px_bounds = outlined
         .iter()
         .map(OutlinedGlyph::px_bounds)
         .reduce(|b, next| Rect {
@@ -67,12 +86,9 @@ fn get_glyph_outlines(glyphs: Vec<Glyph>, font: &FontVec) -> (Vec<OutlinedGlyph>
                 x: b.max.x.max(next.max.x),
                 y: b.max.y.max(next.max.y),
             },
-        })
-    else {
-        panic!("no outlines?")
-    };
+        })?;
 
-    (outlined, px_bounds)
+    Some((outlined, px_bounds))
 }
 
 fn create_image_from_gylphs(
@@ -1,12 +1,20 @@
-use image::DynamicImage;
+use super::ResourceError;
+
+use image::{DynamicImage, Rgba, RgbaImage};
 use serde::Deserialize;
 use serde_json::from_slice;
 use std::{
     collections::HashMap,
     fs::File,
     io::{Read, Result},
+    path::PathBuf,
 };
 
+const NOT_FOUND_PATH: &str = "res/404.png";
+const NOT_FOUND_NAME: &str = "404";
+// shown for every texture that could not be found or opened
+pub(crate) const NOT_FOUND_INDEX: usize = 0;
+
 #[derive(Deserialize)]
 struct Texture {
     name: String,
@@ -27,28 +35,37 @@ pub(crate) struct TextureTable {
 }
 
 impl TextureTable {
-    pub fn from_json(file_path: &str) -> Self {
+    pub fn from_json(file_path: &str) -> std::result::Result<Self, ResourceError> {
         let table_bytes = read_bytes_from_file(file_path)
-            .expect(&("Failed to read bytes from ".to_string() + file_path));
-        from_slice(&table_bytes).expect(&("Failed to parse ".to_string() + file_path))
+            .map_err(|error| ResourceError::Io(PathBuf::from(file_path), error))?;
+        from_slice(&table_bytes)
+            .map_err(|error| ResourceError::Json(PathBuf::from(file_path), error))
     }
 
     pub fn load_images(&self) -> (Vec<DynamicImage>, HashMap<String, usize>) {
         // estimating that sprites consist of ~4 images, therefore reducing allocations
         let estimated_amount = self.textures.len() + self.sprites.len() * 4;
-        let mut images: Vec<DynamicImage> = Vec::with_capacity(estimated_amount);
-        let mut texture_indices: HashMap<String, usize> = HashMap::with_capacity(estimated_amount);
+        let mut images: Vec<DynamicImage> = Vec::with_capacity(estimated_amount + 1);
+        let mut texture_indices: HashMap<String, usize> =
+            HashMap::with_capacity(estimated_amount + 1);
 
-        let mut next_id = 0;
+        let not_found = open_not_found_image();
+        images.push(not_found.clone());
+        texture_indices.insert(NOT_FOUND_NAME.to_owned(), NOT_FOUND_INDEX);
+        let mut next_id = NOT_FOUND_INDEX + 1;
 
         for texture in &self.textures {
-            images.push(open_image(&texture.path));
+            images.push(open_image(&texture.path, &not_found));
             texture_indices.insert(texture.name.clone(), next_id);
             next_id += 1;
         }
 
         for sprite in &self.sprites {
-            images.extend(load_sprite_from_file(&sprite.path, sprite.sprite_size));
+            images.extend(load_sprite_from_file(
+                &sprite.path,
+                sprite.sprite_size,
+                &not_found,
+            ));
             for index in 0..sprite.sprite_size * sprite.sprite_size {
                 texture_indices.insert(sprite.name.clone() + "_" + &index.to_string(), next_id);
                 next_id += 1;
@@ -59,22 +76,40 @@ impl TextureTable {
     }
 }
 
-fn load_sprite_from_file(texture_path: &str, sprite_size: u32) -> Vec<DynamicImage> {
-    let texture_image = open_image(texture_path);
+fn load_sprite_from_file(
+    texture_path: &str,
+    sprite_size: u32,
+    not_found: &DynamicImage,
+) -> Vec<DynamicImage> {
+    let texture_image = open_image(texture_path, not_found);
     let sprite_width = texture_image.width() / sprite_size;
     let sprite_height = texture_image.height() / sprite_size;
 
     crop_sprite(&texture_image, sprite_size, sprite_width, sprite_height)
 }
 
-fn open_image(path: &str) -> DynamicImage {
-    match image::open(path) {
-        Ok(image) => image,
-        Err(e) => {
-            println!("{e}",);
-            image::open("res/404.png").expect("Failed to open texture not found image!")
-        }
-    }
+fn open_image(path: &str, not_found: &DynamicImage) -> DynamicImage {
+    image::open(path).unwrap_or_else(|error| {
+        let error = ResourceError::Image(PathBuf::from(path), error);
+        log::warn!("{error}, using the 404 texture");
+        not_found.clone()
+    })
+}
+
+// a magenta and black checkerboard if even the 404 texture is missing
+fn open_not_found_image() -> DynamicImage {
+    image::open(NOT_FOUND_PATH).unwrap_or_else(|error| {
+        let error = ResourceError::Image(PathBuf::from(NOT_FOUND_PATH), error);
+        log::error!("{error}, using a generated texture");
+
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(8, 8, |x, y| {
+            if (x / 4 + y / 4) % 2 == 0 {
+                Rgba([255, 0, 255, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        }))
+    })
 }
 
 fn crop_sprite(
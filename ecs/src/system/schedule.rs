@@ -0,0 +1,321 @@
+use super::super::{entity::EntityProvider, ECS};
+
+use std::{any::TypeId, collections::HashMap};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Stage {
+    PreUpdate,
+    Update,
+    PostUpdate,
+    Render,
+}
+
+impl Stage {
+    pub const UPDATES: [Self; 3] = [Self::PreUpdate, Self::Update, Self::PostUpdate];
+}
+
+pub trait System<E>: 'static
+where
+    E: 'static,
+{
+    fn run(&mut self, ecs: &mut ECS<E>, provider: &dyn EntityProvider);
+}
+
+struct ScheduledSystem<E>
+where
+    E: 'static,
+{
+    label: TypeId,
+    system: Box<dyn System<E>>,
+    before: Vec<TypeId>,
+    after: Vec<TypeId>,
+    // change tick of the previous run, queries report changes made since then
+    last_run: u64,
+}
+
+// returned by ECS::add_system to constrain the order inside the system's stage
+pub struct SystemConfig<'schedule, E>
+where
+    E: 'static,
+{
+    scheduled: &'schedule mut ScheduledSystem<E>,
+}
+
+impl<E> SystemConfig<'_, E>
+where
+    E: 'static,
+{
+    pub fn before<S>(&mut self) -> &mut Self
+    where
+        S: System<E>,
+    {
+        self.scheduled.before.push(TypeId::of::<S>());
+        self
+    }
+
+    pub fn after<S>(&mut self) -> &mut Self
+    where
+        S: System<E>,
+    {
+        self.scheduled.after.push(TypeId::of::<S>());
+        self
+    }
+}
+
+struct StageSystems<E>
+where
+    E: 'static,
+{
+    systems: Vec<ScheduledSystem<E>>,
+    sorted: bool,
+}
+
+pub(crate) struct Schedule<E>
+where
+    E: 'static,
+{
+    stages: HashMap<Stage, StageSystems<E>>,
+    // removals requested while this schedule was taken out of the ecs, see append
+    removed: Vec<TypeId>,
+}
+
+impl<E> Default for Schedule<E>
+where
+    E: 'static,
+{
+    fn default() -> Self {
+        Self {
+            stages: HashMap::with_capacity(4),
+            removed: Vec::new(),
+        }
+    }
+}
+
+impl<E> Schedule<E>
+where
+    E: 'static,
+{
+    pub fn add_system<S>(&mut self, stage: Stage, system: S) -> SystemConfig<'_, E>
+    where
+        S: System<E>,
+    {
+        let stage_systems = self.stages.entry(stage).or_insert_with(|| StageSystems {
+            systems: Vec::new(),
+            sorted: true,
+        });
+        stage_systems.sorted = false;
+
+        stage_systems.systems.push(ScheduledSystem {
+            label: TypeId::of::<S>(),
+            system: Box::new(system),
+            before: Vec::new(),
+            after: Vec::new(),
+            last_run: 0,
+        });
+
+        SystemConfig {
+            scheduled: stage_systems
+                .systems
+                .last_mut()
+                .expect("System was lost while adding it!"),
+        }
+    }
+
+    pub fn remove_system<S>(&mut self)
+    where
+        S: System<E>,
+    {
+        self.remove_label(TypeId::of::<S>());
+        self.removed.push(TypeId::of::<S>());
+    }
+
+    fn remove_label(&mut self, label: TypeId) {
+        for stage_systems in self.stages.values_mut() {
+            stage_systems
+                .systems
+                .retain(|scheduled| scheduled.label != label);
+        }
+    }
+
+    // moves systems that were added while this schedule was taken out of the ecs
+    pub fn append(&mut self, other: Self) {
+        for label in other.removed {
+            self.remove_label(label);
+        }
+        // nothing is taken out anymore that could still need them
+        self.removed.clear();
+
+        for (stage, mut other_systems) in other.stages {
+            let stage_systems = self.stages.entry(stage).or_insert_with(|| StageSystems {
+                systems: Vec::new(),
+                sorted: true,
+            });
+            stage_systems.sorted = false;
+            stage_systems.systems.append(&mut other_systems.systems);
+        }
+    }
+
+    pub fn run(&mut self, stage: Stage, ecs: &mut ECS<E>, provider: &dyn EntityProvider) {
+        let Some(stage_systems) = self.stages.get_mut(&stage) else {
+            return;
+        };
+
+        if !stage_systems.sorted {
+            sort_systems(&mut stage_systems.systems);
+            stage_systems.sorted = true;
+        }
+
+        for scheduled in &mut stage_systems.systems {
+            scheduled.last_run = ecs.component_manager.begin_run(scheduled.last_run);
+            scheduled.system.run(ecs, provider);
+            ecs.component_manager.end_run();
+        }
+    }
+}
+
+// topological sort that keeps the registration order wherever no constraint applies,
+// constraints may name systems that are added later, so cycles are only found here
+fn sort_systems<E>(systems: &mut Vec<ScheduledSystem<E>>)
+where
+    E: 'static,
+{
+    let count = systems.len();
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut dependencies = vec![0_usize; count];
+
+    for (index, scheduled) in systems.iter().enumerate() {
+        for (other, other_scheduled) in systems.iter().enumerate() {
+            if index == other {
+                continue;
+            }
+
+            // constraints on systems that are not part of this stage are ignored
+            let runs_before = scheduled.before.contains(&other_scheduled.label)
+                || other_scheduled.after.contains(&scheduled.label);
+            if runs_before {
+                dependents[index].push(other);
+                dependencies[other] += 1;
+            }
+        }
+    }
+
+    let mut order = Vec::with_capacity(count);
+    let mut done = vec![false; count];
+    while order.len() < count {
+        let Some(next) = (0..count).find(|index| !done[*index] && dependencies[*index] == 0) else {
+            log::error!(
+                "System ordering constraints contain a cycle, using the registration order"
+            );
+            return;
+        };
+
+        done[next] = true;
+        for dependent in &dependents[next] {
+            dependencies[*dependent] -= 1;
+        }
+        order.push(next);
+    }
+
+    let mut taken: Vec<Option<ScheduledSystem<E>>> = systems.drain(..).map(Some).collect();
+    systems.extend(order.into_iter().filter_map(|index| taken[index].take()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::super::{entity::EntityProvider, testing, ECS},
+        Stage, System,
+    };
+
+    // the names of the systems in the order they ran
+    type Ran = Vec<&'static str>;
+
+    fn record(ecs: &mut ECS<()>, name: &'static str) {
+        if let Some(ran) = ecs.resource_mut::<Ran>() {
+            ran.push(name);
+        }
+    }
+
+    struct First;
+
+    impl System<()> for First {
+        fn run(&mut self, ecs: &mut ECS<()>, _provider: &dyn EntityProvider) {
+            record(ecs, "first");
+        }
+    }
+
+    struct Second;
+
+    impl System<()> for Second {
+        fn run(&mut self, ecs: &mut ECS<()>, _provider: &dyn EntityProvider) {
+            record(ecs, "second");
+        }
+    }
+
+    struct Third;
+
+    impl System<()> for Third {
+        fn run(&mut self, ecs: &mut ECS<()>, _provider: &dyn EntityProvider) {
+            record(ecs, "third");
+        }
+    }
+
+    // removes third while the schedule is taken out of the ecs
+    struct RemoveThird;
+
+    impl System<()> for RemoveThird {
+        fn run(&mut self, ecs: &mut ECS<()>, _provider: &dyn EntityProvider) {
+            record(ecs, "remove third");
+            ecs.remove_system::<Third>();
+        }
+    }
+
+    fn ran(ecs: &mut ECS<()>) -> Ran {
+        ecs.insert_resource(Ran::new());
+        ecs.run_stage(Stage::Update, &testing::Entities(Vec::new()));
+
+        ecs.resource::<Ran>().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn systems_run_in_registration_order_without_constraints() {
+        let mut ecs = testing::ecs();
+        ecs.add_system(Stage::Update, Second);
+        ecs.add_system(Stage::Update, First);
+        ecs.add_system(Stage::Update, Third);
+
+        assert_eq!(ran(&mut ecs), ["second", "first", "third"]);
+        assert_eq!(ran(&mut ecs), ["second", "first", "third"]);
+    }
+
+    #[test]
+    fn constraints_order_systems() {
+        let mut ecs = testing::ecs();
+        ecs.add_system(Stage::Update, Third).after::<Second>();
+        ecs.add_system(Stage::Update, Second);
+        ecs.add_system(Stage::Update, First).before::<Second>();
+
+        assert_eq!(ran(&mut ecs), ["first", "second", "third"]);
+    }
+
+    #[test]
+    fn cycles_fall_back_to_registration_order() {
+        let mut ecs = testing::ecs();
+        ecs.add_system(Stage::Update, First).after::<Second>();
+        ecs.add_system(Stage::Update, Second).after::<First>();
+        ecs.add_system(Stage::Update, Third);
+
+        assert_eq!(ran(&mut ecs), ["first", "second", "third"]);
+    }
+
+    #[test]
+    fn systems_can_be_removed_while_the_schedule_runs() {
+        let mut ecs = testing::ecs();
+        ecs.add_system(Stage::Update, RemoveThird);
+        ecs.add_system(Stage::Update, Third);
+
+        // the schedule that is running does not see the removal yet
+        assert_eq!(ran(&mut ecs), ["remove third", "third"]);
+        assert_eq!(ran(&mut ecs), ["remove third"]);
+    }
+}
@@ -0,0 +1,95 @@
+use super::{Component, Entity, EntityProvider, ECS};
+
+use ash::{vk, Device};
+use image::{ImageBuffer, Rgba};
+use rendering::{ImageData, ImageView, Renderer, MVP};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::Cell,
+    env, fs, process,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static NEXT_TABLE: AtomicUsize = AtomicUsize::new(0);
+
+// an ecs without textures, tests run in parallel so every one gets its own table
+pub fn ecs() -> ECS<()> {
+    let path = env::temp_dir().join(format!(
+        "arcanaith_test_table_{}_{}.json",
+        process::id(),
+        NEXT_TABLE.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&path, r#"{"textures": [], "sprites": []}"#).expect("Failed to write texture table!");
+
+    let ecs = ECS::create(&path.to_string_lossy(), "fonts").expect("Failed to create test ecs!");
+    let _ = fs::remove_file(path);
+
+    ecs
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Health(pub u32);
+
+impl Component for Health {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Armor(pub u32);
+
+impl Component for Armor {}
+
+// a fixed list of entities without a player
+pub struct Entities(pub Vec<Entity>);
+
+impl EntityProvider for Entities {
+    fn get_entities(&self) -> &[Entity] {
+        &self.0
+    }
+
+    fn get_player(&self) -> Option<Entity> {
+        None
+    }
+}
+
+// hands out images without a gpu, they must never be destroyed
+pub struct FakeRenderer {
+    device: Rc<Device>,
+    pub created: Cell<usize>,
+    pub retired: Vec<ImageData>,
+    pub drawn: Vec<usize>,
+}
+
+impl Default for FakeRenderer {
+    fn default() -> Self {
+        // every device function panics, none of them is called by the ecs without a real renderer
+        let device = unsafe { Device::load_with(|_| std::ptr::null(), vk::Device::null()) };
+
+        Self {
+            device: Rc::new(device),
+            created: Cell::new(0),
+            retired: Vec::new(),
+            drawn: Vec::new(),
+        }
+    }
+}
+
+impl Renderer for FakeRenderer {
+    fn draw(&mut self, textures: &[ImageView], _positions: &[MVP]) {
+        self.drawn.push(textures.len());
+    }
+
+    fn create_image_data(&self, _image: ImageBuffer<Rgba<u8>, Vec<u8>>) -> ImageData {
+        self.created.set(self.created.get() + 1);
+
+        ImageData::create(
+            vk::Image::null(),
+            vk::DeviceMemory::null(),
+            vk::ImageView::null(),
+            self.device.clone(),
+        )
+    }
+
+    fn destroy_image_data(&mut self, image_data: ImageData) {
+        self.retired.push(image_data);
+    }
+}
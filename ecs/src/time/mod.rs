@@ -0,0 +1,270 @@
+use std::time::{Duration, Instant};
+
+// more steps than this per frame would only make a slow frame slower
+const MAX_STEPS_PER_UPDATE: u32 = 5;
+// far beyond what the step clamp can keep up with, keeps mul_f32 in advance from overflowing
+const MAX_SCALE: f32 = 100.0;
+
+// simulation clock, systems read it as a resource and scale by delta instead of counting frames
+pub struct Time {
+    step: Duration,
+    elapsed: Duration,
+    // real time that was not simulated yet
+    accumulator: Duration,
+    last_update: Option<Instant>,
+    // how far the renderer is between the previous and the current step
+    alpha: f32,
+    // simulated seconds per real second, below 1 is slow motion
+    scale: f32,
+    // no steps run while paused, input and rendering go on
+    paused: bool,
+}
+
+impl Time {
+    #[must_use]
+    pub fn new(step: Duration) -> Self {
+        Self {
+            step,
+            elapsed: Duration::ZERO,
+            accumulator: Duration::ZERO,
+            last_update: None,
+            alpha: 1.0,
+            scale: 1.0,
+            paused: false,
+        }
+    }
+
+    // length of one simulation step
+    #[must_use]
+    pub fn delta(&self) -> Duration {
+        self.step
+    }
+
+    #[must_use]
+    pub fn delta_seconds(&self) -> f32 {
+        self.step.as_secs_f32()
+    }
+
+    // simulated time, only advances while steps run
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    #[must_use]
+    pub fn elapsed_seconds(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    #[must_use]
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    #[must_use]
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    // the step stays the same, a lower scale only runs fewer of them, nan and infinity reset it
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = if scale.is_finite() {
+            scale.clamp(0.0, MAX_SCALE)
+        } else {
+            1.0
+        };
+    }
+
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    // returns how many steps are due since the previous call
+    pub(crate) fn advance(&mut self) -> u32 {
+        let now = Instant::now();
+        let frame_time = self
+            .last_update
+            .map_or(Duration::ZERO, |last_update| now - last_update);
+        self.last_update = Some(now);
+
+        self.advance_by(frame_time)
+    }
+
+    // advance with a given frame time instead of the real one
+    pub(crate) fn advance_by(&mut self, frame_time: Duration) -> u32 {
+        // time that passes while paused is never simulated, alpha keeps the frozen frame
+        if self.paused {
+            return 0;
+        }
+
+        self.accumulator += frame_time.mul_f32(self.scale);
+
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < MAX_STEPS_PER_UPDATE {
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        // the simulation fell behind, dropping time is better than catching up forever
+        if steps == MAX_STEPS_PER_UPDATE {
+            self.accumulator = self.accumulator.min(self.step);
+        }
+
+        self.alpha = self.accumulator.as_secs_f32() / self.step.as_secs_f32();
+        steps
+    }
+
+    pub(crate) fn tick(&mut self) {
+        self.elapsed += self.step;
+    }
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new(Duration::from_secs_f64(1.0 / 60.0))
+    }
+}
+
+// counts down in simulated time, see Time::delta
+pub struct Timer {
+    duration: Duration,
+    elapsed: Duration,
+    repeating: bool,
+}
+
+impl Timer {
+    #[must_use]
+    pub fn new(duration: Duration, repeating: bool) -> Self {
+        Self {
+            duration,
+            elapsed: Duration::ZERO,
+            repeating,
+        }
+    }
+
+    // true once the duration has passed, repeating timers start over
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        if !self.repeating && self.is_finished() {
+            return false;
+        }
+
+        self.elapsed += delta;
+        if self.elapsed < self.duration {
+            return false;
+        }
+
+        if self.repeating && !self.duration.is_zero() {
+            self.elapsed = self.elapsed.saturating_sub(self.duration);
+        }
+        true
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.elapsed)
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Time, Timer, MAX_SCALE, MAX_STEPS_PER_UPDATE};
+
+    use std::time::Duration;
+
+    const STEP: Duration = Duration::from_millis(10);
+
+    #[test]
+    fn leftover_time_carries_over_to_the_next_frame() {
+        let mut time = Time::new(STEP);
+
+        assert_eq!(time.advance_by(Duration::from_millis(25)), 2);
+        assert!((time.alpha() - 0.5).abs() < 1e-4);
+
+        assert_eq!(time.advance_by(Duration::from_millis(5)), 1);
+        assert!(time.alpha().abs() < 1e-4);
+
+        assert_eq!(time.advance_by(Duration::from_millis(4)), 0);
+        assert!((time.alpha() - 0.4).abs() < 1e-4);
+    }
+
+    #[test]
+    fn slow_frames_are_clamped() {
+        let mut time = Time::new(STEP);
+
+        assert_eq!(
+            time.advance_by(Duration::from_secs(1)),
+            MAX_STEPS_PER_UPDATE
+        );
+        assert!((0.0..=1.0).contains(&time.alpha()));
+
+        // the dropped time is not caught up later
+        assert!(time.advance_by(Duration::ZERO) <= 1);
+    }
+
+    #[test]
+    fn scale_and_pause_change_the_simulated_time() {
+        let mut time = Time::new(STEP);
+
+        time.set_scale(0.5);
+        assert_eq!(time.advance_by(Duration::from_millis(40)), 2);
+
+        time.set_paused(true);
+        let alpha = time.alpha();
+        assert_eq!(time.advance_by(Duration::from_millis(100)), 0);
+        assert!((time.alpha() - alpha).abs() < f32::EPSILON);
+
+        time.set_paused(false);
+        assert_eq!(time.advance_by(Duration::from_millis(20)), 1);
+    }
+
+    #[test]
+    fn scales_are_kept_finite() {
+        let mut time = Time::new(STEP);
+
+        time.set_scale(-2.0);
+        assert!(time.scale().abs() < f32::EPSILON);
+
+        time.set_scale(f32::MAX);
+        assert!((time.scale() - MAX_SCALE).abs() < f32::EPSILON);
+
+        for scale in [f32::INFINITY, f32::NAN] {
+            time.set_scale(scale);
+            assert!((time.scale() - 1.0).abs() < f32::EPSILON);
+        }
+
+        time.set_scale(f32::MAX);
+        assert_eq!(
+            time.advance_by(Duration::from_secs(10)),
+            MAX_STEPS_PER_UPDATE
+        );
+    }
+
+    #[test]
+    fn timers_finish_once_or_repeat() {
+        let mut once = Timer::new(STEP, false);
+        assert!(!once.tick(Duration::from_millis(6)));
+        assert!(once.tick(Duration::from_millis(6)));
+        assert!(!once.tick(Duration::from_millis(6)));
+        assert!(once.is_finished());
+
+        let mut repeating = Timer::new(STEP, true);
+        assert!(repeating.tick(Duration::from_millis(12)));
+        assert_eq!(repeating.remaining(), Duration::from_millis(8));
+        assert!(!repeating.tick(Duration::from_millis(6)));
+        assert!(repeating.tick(Duration::from_millis(2)));
+    }
+}
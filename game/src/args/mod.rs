@@ -0,0 +1,241 @@
+use crate::config::Config;
+
+use log::LevelFilter;
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+};
+
+pub const USAGE: &str = "Usage: game [OPTIONS]
+
+Options:
+  --windowed              start in a window, even if fullscreen is configured
+  --fps <FPS>             frame rate limit, overrides the config
+  --resource-dir <DIR>    directory with res and shaders, the config and saves are kept there too
+  --start-scene <SCENE>   menu, game or continue, skips the main menu
+  --seed <SEED>           seed of the random generator, to reproduce a run
+  --log-level <LEVEL>     off, error, warn, info, debug or trace, F6 changes it while running
+  --validation            enable the vulkan validation layers, always on in debug builds
+  --help                  print this help";
+
+#[derive(Clone, Copy, Default)]
+pub enum StartScene {
+    #[default]
+    MainMenu,
+    NewGame,
+    ContinueGame,
+}
+
+#[derive(Debug)]
+pub enum ArgsError {
+    Unknown(String),
+    MissingValue(&'static str),
+    InvalidValue(&'static str, String),
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            ArgsError::Unknown(arg) => write!(formatter, "unknown argument: {arg}"),
+            ArgsError::MissingValue(flag) => write!(formatter, "{flag} needs a value"),
+            ArgsError::InvalidValue(flag, value) => {
+                write!(formatter, "invalid value for {flag}: {value}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+// command line flags, they win over the config file
+#[derive(Default)]
+pub struct Args {
+    pub help: bool,
+    pub windowed: bool,
+    pub fps: Option<u32>,
+    pub resource_dir: Option<PathBuf>,
+    pub start_scene: StartScene,
+    pub seed: Option<u64>,
+    pub log_level: Option<LevelFilter>,
+    pub validation: bool,
+}
+
+impl Args {
+    // accepts "--flag value" as well as "--flag=value"
+    pub fn parse<I>(args: I) -> Result<Self, ArgsError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_owned(), Some(value.to_owned())),
+                None => (arg, None),
+            };
+
+            match flag.as_str() {
+                "--help" | "-h" => parsed.help = true,
+                "--windowed" => parsed.windowed = true,
+                "--validation" => parsed.validation = true,
+                "--fps" => {
+                    let value = value("--fps", inline_value, &mut args)?;
+                    parsed.fps = Some(parse_value("--fps", value)?);
+                }
+                "--resource-dir" => {
+                    let value = value("--resource-dir", inline_value, &mut args)?;
+                    parsed.resource_dir = Some(PathBuf::from(value));
+                }
+                "--start-scene" => {
+                    let value = value("--start-scene", inline_value, &mut args)?;
+                    parsed.start_scene = match value.as_str() {
+                        "menu" => StartScene::MainMenu,
+                        "game" => StartScene::NewGame,
+                        "continue" => StartScene::ContinueGame,
+                        _ => return Err(ArgsError::InvalidValue("--start-scene", value)),
+                    };
+                }
+                "--seed" => {
+                    let value = value("--seed", inline_value, &mut args)?;
+                    parsed.seed = Some(parse_value("--seed", value)?);
+                }
+                "--log-level" => {
+                    let value = value("--log-level", inline_value, &mut args)?;
+                    parsed.log_level = Some(parse_value("--log-level", value)?);
+                }
+                _ => return Err(ArgsError::Unknown(flag)),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    // overridden values are checked the same way as the ones from the file,
+    // apply them to a copy so they are not saved with it
+    pub fn apply(&self, config: &mut Config) {
+        if self.windowed {
+            config.fullscreen = false;
+        }
+
+        if let Some(fps) = self.fps {
+            config.fps = fps;
+        }
+
+        if let Some(level) = self.log_level {
+            config.log.level = level;
+        }
+
+        for error in config.validate() {
+            log::warn!("{error}");
+        }
+    }
+
+    // debug builds always validate
+    #[must_use]
+    pub fn validation_enabled(&self) -> bool {
+        self.validation || cfg!(debug_assertions)
+    }
+}
+
+fn value<I>(
+    flag: &'static str,
+    inline_value: Option<String>,
+    args: &mut I,
+) -> Result<String, ArgsError>
+where
+    I: Iterator<Item = String>,
+{
+    inline_value
+        .or_else(|| args.next())
+        .ok_or(ArgsError::MissingValue(flag))
+}
+
+fn parse_value<T>(flag: &'static str, value: String) -> Result<T, ArgsError>
+where
+    T: std::str::FromStr,
+{
+    value
+        .parse()
+        .map_err(|_| ArgsError::InvalidValue(flag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, ArgsError> {
+        Args::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn parses_values_after_the_flag_and_inline() {
+        let args = parse(&[
+            "--windowed",
+            "--fps",
+            "144",
+            "--seed=42",
+            "--start-scene",
+            "game",
+            "--log-level=debug",
+            "--resource-dir",
+            "assets",
+        ])
+        .expect("Failed to parse valid args!");
+
+        assert!(args.windowed);
+        assert_eq!(args.fps, Some(144));
+        assert_eq!(args.seed, Some(42));
+        assert!(matches!(args.start_scene, StartScene::NewGame));
+        assert_eq!(args.log_level, Some(LevelFilter::Debug));
+        assert_eq!(args.resource_dir, Some(PathBuf::from("assets")));
+        assert!(!args.help);
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(matches!(
+            parse(&["--fps"]),
+            Err(ArgsError::MissingValue("--fps"))
+        ));
+        assert!(matches!(
+            parse(&["--fps", "fast"]),
+            Err(ArgsError::InvalidValue("--fps", _))
+        ));
+        assert!(matches!(
+            parse(&["--start-scene=credits"]),
+            Err(ArgsError::InvalidValue("--start-scene", _))
+        ));
+        assert!(matches!(
+            parse(&["--fullscreen"]),
+            Err(ArgsError::Unknown(_))
+        ));
+    }
+
+    #[test]
+    fn overrides_only_change_the_copy() {
+        let file_config = Config {
+            fullscreen: true,
+            ..Config::default()
+        };
+        let args = parse(&["--windowed", "--fps=30"]).expect("Failed to parse valid args!");
+
+        let mut config = file_config.clone();
+        args.apply(&mut config);
+
+        assert!(!config.fullscreen);
+        assert_eq!(config.fps, 30);
+        assert!(file_config.fullscreen);
+        assert_eq!(file_config.fps, Config::default().fps);
+    }
+
+    #[test]
+    fn invalid_overrides_fall_back_to_defaults() {
+        let args = parse(&["--fps=0"]).expect("Failed to parse valid args!");
+
+        let mut config = Config::default();
+        args.apply(&mut config);
+
+        assert_eq!(config.fps, Config::default().fps);
+    }
+}
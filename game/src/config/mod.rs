@@ -0,0 +1,276 @@
+use crate::logging::LogConfig;
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+    time::Duration,
+};
+
+// frame rates the settings menu cycles through
+const FPS_OPTIONS: [u32; 4] = [30, 60, 120, 144];
+const MAX_FPS: u32 = 1000;
+const MAX_TICK_RATE: u32 = 1000;
+const MAX_FRAMES_IN_FLIGHT: usize = 3;
+
+// options that can be changed without recompiling, stored as an ecs resource
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub title: String,
+    pub fullscreen: bool,
+    pub fps: u32,
+    // simulation steps per second, independent of the frame rate
+    pub tick_rate: u32,
+    pub frames_in_flight: usize,
+    pub paths: Paths,
+    pub log: LogConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            title: "Arcanaith".to_owned(),
+            fullscreen: false,
+            fps: 60,
+            tick_rate: 60,
+            frames_in_flight: 2,
+            paths: Paths::default(),
+            log: LogConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Paths {
+    pub icon: String,
+    pub vertex_shader: String,
+    pub fragment_shader: String,
+    pub texture_table: String,
+    pub fonts: String,
+    pub main_menu: String,
+    pub settings_menu: String,
+    pub pause_menu: String,
+    pub overlay: String,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            icon: "res/icon.png".to_owned(),
+            vertex_shader: "shaders/vert.spv".to_owned(),
+            fragment_shader: "shaders/frag.spv".to_owned(),
+            texture_table: "res/texture_table.json".to_owned(),
+            fonts: "res/fonts".to_owned(),
+            main_menu: "res/scenes/main_menu.json".to_owned(),
+            settings_menu: "res/scenes/settings_menu.json".to_owned(),
+            pause_menu: "res/scenes/pause_menu.json".to_owned(),
+            overlay: "res/scenes/overlay.json".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Invalid(&'static str, String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(formatter, "config io failed: {error}"),
+            ConfigError::Json(error) => write!(formatter, "config is not valid: {error}"),
+            ConfigError::Invalid(field, value) => {
+                write!(formatter, "invalid {field}: {value}, using the default")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    // missing values are filled with defaults, a missing file is created
+    pub fn load<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            let config = Self::default();
+            config.save(path);
+            return config;
+        }
+
+        // a broken file is kept, so the player can fix it
+        let mut config = Self::read_from(path).unwrap_or_else(|error| {
+            log::error!("Failed to load {}, using defaults: {error}", path.display());
+            Self::default()
+        });
+
+        for error in config.validate() {
+            log::warn!("{error}");
+        }
+
+        config
+    }
+
+    pub fn read_from<P>(path: P) -> Result<Self, ConfigError>
+    where
+        P: AsRef<Path>,
+    {
+        serde_json::from_str(&fs::read_to_string(path).map_err(ConfigError::Io)?)
+            .map_err(ConfigError::Json)
+    }
+
+    pub fn write_to<P>(&self, path: P) -> Result<(), ConfigError>
+    where
+        P: AsRef<Path>,
+    {
+        let json = serde_json::to_string_pretty(self).map_err(ConfigError::Json)?;
+
+        fs::write(path, json).map_err(ConfigError::Io)
+    }
+
+    pub fn save<P>(&self, path: P)
+    where
+        P: AsRef<Path>,
+    {
+        if let Err(error) = self.write_to(path) {
+            log::error!("Failed to save the config: {error}");
+        }
+    }
+
+    // resets values that can not work to their defaults
+    pub fn validate(&mut self) -> Vec<ConfigError> {
+        let default = Self::default();
+        let mut errors = Vec::new();
+
+        if self.title.trim().is_empty() {
+            errors.push(ConfigError::Invalid("title", format!("{:?}", self.title)));
+            self.title = default.title;
+        }
+
+        if !(1..=MAX_FPS).contains(&self.fps) {
+            errors.push(ConfigError::Invalid("fps", self.fps.to_string()));
+            self.fps = default.fps;
+        }
+
+        if !(1..=MAX_TICK_RATE).contains(&self.tick_rate) {
+            errors.push(ConfigError::Invalid(
+                "tick_rate",
+                self.tick_rate.to_string(),
+            ));
+            self.tick_rate = default.tick_rate;
+        }
+
+        if !(1..=MAX_FRAMES_IN_FLIGHT).contains(&self.frames_in_flight) {
+            errors.push(ConfigError::Invalid(
+                "frames_in_flight",
+                self.frames_in_flight.to_string(),
+            ));
+            self.frames_in_flight = default.frames_in_flight;
+        }
+
+        errors
+    }
+
+    pub fn frame_time(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / f64::from(self.fps))
+    }
+
+    pub fn tick_time(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / f64::from(self.tick_rate))
+    }
+
+    // the next frame rate of the settings menu, custom values start over at the first
+    pub fn next_fps(&self) -> u32 {
+        FPS_OPTIONS
+            .iter()
+            .copied()
+            .find(|fps| *fps > self.fps)
+            .unwrap_or(FPS_OPTIONS[0])
+    }
+
+    pub fn fullscreen_text(&self) -> String {
+        let state = if self.fullscreen { "On" } else { "Off" };
+        format!("Fullscreen: {state}")
+    }
+
+    pub fn fps_text(&self) -> String {
+        format!("FPS: {}", self.fps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_values_are_filled_with_defaults() {
+        let config: Config = serde_json::from_str(r#"{"fps": 144, "paths": {"icon": "icon.png"}}"#)
+            .expect("Failed to parse a partial config!");
+
+        assert_eq!(config.fps, 144);
+        assert_eq!(config.tick_rate, Config::default().tick_rate);
+        assert_eq!(config.paths.icon, "icon.png");
+        assert_eq!(config.paths.fonts, Paths::default().fonts);
+    }
+
+    #[test]
+    fn validate_resets_invalid_values() {
+        let mut config = Config {
+            title: "  ".to_owned(),
+            fps: 0,
+            tick_rate: MAX_TICK_RATE + 1,
+            frames_in_flight: 0,
+            ..Config::default()
+        };
+
+        let errors = config.validate();
+
+        assert_eq!(errors.len(), 4);
+        assert_eq!(config.title, Config::default().title);
+        assert_eq!(config.fps, Config::default().fps);
+        assert_eq!(config.tick_rate, Config::default().tick_rate);
+        assert_eq!(config.frames_in_flight, Config::default().frames_in_flight);
+        assert!(Config::default().validate().is_empty());
+    }
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let path = std::env::temp_dir().join("arcanaith_config_test.json");
+        let config = Config {
+            fullscreen: true,
+            fps: 120,
+            ..Config::default()
+        };
+
+        config.write_to(&path).expect("Failed to write the config!");
+        let read = Config::read_from(&path).expect("Failed to read the config!");
+        let _ = fs::remove_file(&path);
+
+        assert!(read.fullscreen);
+        assert_eq!(read.fps, 120);
+    }
+
+    #[test]
+    fn next_fps_cycles_through_the_options() {
+        let mut config = Config {
+            fps: 144,
+            ..Config::default()
+        };
+        assert_eq!(config.next_fps(), 30);
+
+        config.fps = 60;
+        assert_eq!(config.next_fps(), 120);
+
+        // custom values continue with the next larger option
+        config.fps = 75;
+        assert_eq!(config.next_fps(), 120);
+    }
+}
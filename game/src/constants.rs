@@ -1,7 +1,5 @@
-pub const TITLE: &str = "Arcanaith";
-pub const ICONPATH: &str = "res/icon.png";
-pub const FRAGSHADER: &str = "shaders/frag.spv";
-pub const VERTSHADER: &str = "shaders/vert.spv";
-pub const FPS: u32 = 60;
-pub const FRAMES_IN_FLIGHT: usize = 2;
-pub const FULLSCREEN: bool = false;
+pub const CONFIGPATH: &str = "config.json";
+pub const SAVEPATH: &str = "saves/continue.json";
+pub const INSPECTORDUMP: &str = "inspector_dump.txt";
+pub const FRAMESTATSCSV: &str = "frame_stats.csv";
+pub const CRASHREPORTDIR: &str = "crash_reports";
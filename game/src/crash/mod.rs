@@ -0,0 +1,224 @@
+use crate::{
+    config::Config, constants::CRASHREPORTDIR, game::DebugKeyHandler, logging, panel::TextPanel,
+    GameEvent,
+};
+
+use ecs::ECS;
+use glam::Vec2;
+use std::{
+    backtrace::Backtrace,
+    fmt::Write,
+    fs, io,
+    panic::{self, PanicHookInfo},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use winit::keyboard::Key;
+
+// holds the path of the report the player was not told about yet
+const PENDING_FILE: &str = "pending";
+
+// what the game was doing, kept up to date so the panic hook can read it
+static SCENE: Mutex<String> = Mutex::new(String::new());
+static CONFIG: Mutex<String> = Mutex::new(String::new());
+static ENTITY_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_scene(scene: String) {
+    if let Ok(mut current) = SCENE.lock() {
+        *current = scene;
+    }
+}
+
+pub fn set_entity_count(count: usize) {
+    ENTITY_COUNT.store(count, Ordering::Relaxed);
+}
+
+pub fn set_config(config: &Config) {
+    let json = serde_json::to_string_pretty(config)
+        .unwrap_or_else(|error| format!("failed to serialize the config: {error}"));
+
+    if let Ok(mut current) = CONFIG.lock() {
+        *current = json;
+    }
+}
+
+// logs the panic and writes a report into the crash report dir
+pub fn install_hook() {
+    panic::set_hook(Box::new(|panic_info| {
+        let message = panic_message(panic_info);
+        log::error!("{message}");
+
+        match write_report(&message) {
+            Ok(path) => log::error!("Crash report written to {}", path.display()),
+            Err(error) => log::error!("Failed to write the crash report: {error}"),
+        }
+
+        log::logger().flush();
+    }));
+}
+
+// the report of the previous run, only returned once
+fn take_pending_report() -> Option<PathBuf> {
+    let pending = Path::new(CRASHREPORTDIR).join(PENDING_FILE);
+    let report = fs::read_to_string(&pending).ok()?;
+
+    if let Err(error) = fs::remove_file(&pending) {
+        log::warn!("Failed to remove {}: {error}", pending.display());
+    }
+
+    Some(PathBuf::from(report.trim()))
+}
+
+fn panic_message(panic_info: &PanicHookInfo) -> String {
+    let payload = if let Some(payload) = panic_info.payload().downcast_ref::<&'static str>() {
+        (*payload).to_owned()
+    } else if let Some(payload) = panic_info.payload().downcast_ref::<String>() {
+        payload.clone()
+    } else {
+        "unknown panic".to_owned()
+    };
+
+    match panic_info.location() {
+        Some(location) => format!("{payload} at {location}"),
+        None => payload,
+    }
+}
+
+fn write_report(message: &str) -> io::Result<PathBuf> {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (date, time) = utc_date_time(seconds);
+
+    fs::create_dir_all(CRASHREPORTDIR)?;
+    let path =
+        Path::new(CRASHREPORTDIR).join(format!("crash_{date}_{}.txt", time.replace(':', "-")));
+
+    fs::write(&path, report(message, &format!("{date} {time} UTC")))?;
+    fs::write(
+        Path::new(CRASHREPORTDIR).join(PENDING_FILE),
+        path.to_string_lossy().as_bytes(),
+    )?;
+
+    Ok(path)
+}
+
+// the locks are only tried, the panic might have happened while one was held
+fn report(message: &str, timestamp: &str) -> String {
+    let scene = SCENE
+        .try_lock()
+        .map_or_else(|_| "unknown".to_owned(), |scene| scene.clone());
+    let config = CONFIG
+        .try_lock()
+        .map_or_else(|_| "unknown".to_owned(), |config| config.clone());
+
+    let mut report = String::new();
+    let _ = writeln!(report, "time: {timestamp}");
+    let _ = writeln!(report, "message: {message}");
+    let _ = writeln!(
+        report,
+        "thread: {}",
+        thread::current().name().unwrap_or("unnamed")
+    );
+    let _ = writeln!(report, "scene: {scene}");
+    let _ = writeln!(report, "entities: {}", ENTITY_COUNT.load(Ordering::Relaxed));
+
+    let _ = writeln!(report, "\nbacktrace:\n{}", Backtrace::force_capture());
+
+    let _ = writeln!(report, "recent log lines:");
+    for line in logging::recent_lines() {
+        let _ = writeln!(report, "{line}");
+    }
+
+    let _ = writeln!(report, "\nconfig:\n{config}");
+
+    report
+}
+
+// "2024-01-31" and "12:00:00", without pulling in a date crate
+fn utc_date_time(seconds: u64) -> (String, String) {
+    let days = seconds / 86_400;
+    let time = seconds % 86_400;
+
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let shifted = days + 719_468;
+    let era = shifted / 146_097;
+    let day_of_era = shifted % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (
+        format!("{year:04}-{month:02}-{day:02}"),
+        format!(
+            "{:02}:{:02}:{:02}",
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        ),
+    )
+}
+
+// tells the player about a crash of the previous run, closed by any key
+pub struct CrashNotice {
+    panel: TextPanel,
+}
+
+impl Default for CrashNotice {
+    fn default() -> Self {
+        Self {
+            panel: TextPanel::new(Vec2 { x: 0.0, y: -0.9 }, 1.8),
+        }
+    }
+}
+
+impl CrashNotice {
+    pub fn panel(&self) -> &TextPanel {
+        &self.panel
+    }
+
+    pub fn show_pending(&mut self, ecs: &mut ECS<GameEvent>) {
+        let Some(report) = take_pending_report() else {
+            return;
+        };
+        log::warn!(
+            "The previous run crashed, see {} for details",
+            report.display()
+        );
+
+        self.panel.toggle(ecs);
+        self.panel.set_lines(
+            ecs,
+            vec![
+                "The game crashed last time.".to_owned(),
+                format!("A report was saved to {}", report.display()),
+                "Press any key to continue".to_owned(),
+            ],
+        );
+    }
+}
+
+impl DebugKeyHandler for CrashNotice {
+    // while the notice is open, any key only closes it
+    fn handle_key(&mut self, ecs: &mut ECS<GameEvent>, _key: &Key) -> bool {
+        if !self.panel.is_open() {
+            return false;
+        }
+
+        self.panel.toggle(ecs);
+        true
+    }
+}
@@ -1,7 +1,9 @@
 use super::{
-    event::{UserEventHandler, WindowEventHandler},
+    event::{DebugKeyHandler, UserEventHandler, WindowEventHandler},
     Game, GameEvent,
 };
+use crate::logging;
+
 use rendering::RenderAPI;
 use winit::{
     application::ApplicationHandler, event::WindowEvent, event_loop::ActiveEventLoop,
@@ -15,13 +17,23 @@ impl<API: RenderAPI> ApplicationHandler<GameEvent> for Game<API> {
 
     fn user_event(&mut self, event_loop: &ActiveEventLoop, event: GameEvent) {
         match event {
-            GameEvent::NewGame => self.load_new_game(),
+            GameEvent::NewGame | GameEvent::ContinueGame | GameEvent::MainMenu => {
+                self.start_transition(event);
+            }
 
             GameEvent::ExitGame => self.exit(event_loop),
 
             GameEvent::SettingsMenu => self.load_settings_menu(),
 
-            GameEvent::MainMenu => self.load_main_menu(),
+            GameEvent::Back => self.load_previous_scene(),
+
+            GameEvent::Pause => self.pause_game(),
+
+            GameEvent::Resume => self.resume_game(),
+
+            GameEvent::ToggleFullscreen => self.toggle_fullscreen(),
+
+            GameEvent::CycleFps => self.cycle_fps(),
         }
     }
 
@@ -36,13 +48,31 @@ impl<API: RenderAPI> ApplicationHandler<GameEvent> for Game<API> {
 
             WindowEvent::RedrawRequested => self.redraw_requested(),
 
+            // nobody is watching the game while another window is focused
+            WindowEvent::Focused(false) => self.pause_game(),
+
             WindowEvent::KeyboardInput {
                 event,
                 is_synthetic: false,
                 ..
-            } => self
-                .ecs
-                .update_keyboard_input(event.state, event.logical_key),
+            } => {
+                // the crash notice goes first, it swallows any key while it is open
+                let handlers: [&mut dyn DebugKeyHandler; 4] = [
+                    &mut self.crash_notice,
+                    &mut self.inspector,
+                    &mut self.stats,
+                    &mut logging::LevelKey,
+                ];
+                let consumed = event.state.is_pressed()
+                    && handlers
+                        .into_iter()
+                        .any(|handler| handler.handle_key(&mut self.ecs, &event.logical_key));
+
+                if !consumed {
+                    self.ecs
+                        .update_keyboard_input(event.state, event.logical_key);
+                }
+            }
 
             WindowEvent::CursorMoved {
                 device_id,
@@ -68,10 +98,11 @@ impl<API: RenderAPI> ApplicationHandler<GameEvent> for Game<API> {
 
             WindowEvent::Moved(_)
             | WindowEvent::Resized(_)
+            | WindowEvent::Focused(true)
             | WindowEvent::CursorEntered { device_id: _ }
             | WindowEvent::CursorLeft { device_id: _ } => (),
 
-            _ => println!("unprocessed event: {event:?}"),
+            _ => log::trace!("unprocessed event: {event:?}"),
         }
     }
 }
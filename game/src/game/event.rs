@@ -1,60 +1,209 @@
 use crate::{
-    scenes::{self, MainMenu, Menu, Scene, SettingsMenu},
+    config::Config,
+    constants::SAVEPATH,
+    crash,
+    panel::PanelScene,
+    scenes::{self, MainMenu, Menu, PauseMenu, Scene, SettingsMenu},
+    stats::FrameSample,
+    transition::{Transition, TransitionKind},
     Game,
 };
 
+use ecs::{RenderTimings, ScreenEffect, Snapshot, ECS};
 use rendering::RenderAPI;
 use std::{thread, time::Instant};
+use winit::keyboard::Key;
 
 #[derive(Debug)]
 pub enum GameEvent {
     NewGame,
+    ContinueGame,
     ExitGame,
     SettingsMenu,
     MainMenu,
+    // closes the top scene, e.g. settings that were opened from the pause menu
+    Back,
+    Pause,
+    Resume,
+    ToggleFullscreen,
+    CycleFps,
 }
 
 pub trait UserEventHandler {
     fn load_settings_menu(&mut self);
     fn load_main_menu(&mut self);
     fn load_new_game(&mut self);
+    fn load_continue_game(&mut self);
+    fn load_previous_scene(&mut self);
+    fn pause_game(&mut self);
+    fn resume_game(&mut self);
+    fn toggle_fullscreen(&mut self);
+    fn cycle_fps(&mut self);
+    fn start_transition(&mut self, event: GameEvent);
+    // true if the next scene can be shown right away, see Scene::is_ready
+    fn switch_scene(&mut self, event: GameEvent) -> bool;
+}
+
+// debug overlays see pressed keys before the scene does
+pub trait DebugKeyHandler {
+    // true if the key was meant for the handler and should not reach the scene
+    fn handle_key(&mut self, ecs: &mut ECS<GameEvent>, key: &Key) -> bool;
 }
 
 pub trait WindowEventHandler {
     fn redraw_requested(&mut self);
+    fn update_transition(&mut self) -> bool;
 }
 
 impl<API: RenderAPI> UserEventHandler for Game<API> {
+    // settings open on top, closing them returns to the same state
     fn load_settings_menu(&mut self) {
-        match self.current_scene {
-            Scene::Menu(Menu::MainMenu(_)) => self.current_scene.destroy(&mut self.ecs),
-            _ => panic!("SettingsMenu event should not have been send!"),
+        match SettingsMenu::create(&mut self.ecs) {
+            Ok(settings_menu) => {
+                let settings_menu = Scene::Menu(Menu::SettingsMenu(settings_menu));
+                self.scenes.push(&mut self.ecs, settings_menu);
+            }
+            Err(error) => log::error!("Failed to load the settings menu: {error}"),
         }
-
-        self.current_scene = Scene::Menu(Menu::SettingsMenu(SettingsMenu::create(&mut self.ecs)));
     }
 
     fn load_main_menu(&mut self) {
-        self.current_scene.destroy(&mut self.ecs);
+        self.scenes.clear(&mut self.ecs);
 
-        self.current_scene = Scene::Menu(Menu::MainMenu(MainMenu::create(&mut self.ecs)));
+        match MainMenu::create(&mut self.ecs) {
+            Ok(main_menu) => {
+                self.scenes
+                    .push(&mut self.ecs, Scene::Menu(Menu::MainMenu(main_menu)));
+            }
+            Err(error) => {
+                log::error!("Failed to load the main menu, starting a new game: {error}");
+                self.load_new_game();
+            }
+        }
     }
 
     fn load_new_game(&mut self) {
-        self.current_scene.destroy(&mut self.ecs);
+        self.scenes.clear(&mut self.ecs);
+
+        let game = Scene::Game(scenes::Game::create(&mut self.ecs));
+        self.scenes.push(&mut self.ecs, game);
+    }
+
+    fn load_continue_game(&mut self) {
+        self.scenes.clear(&mut self.ecs);
+
+        let game = Snapshot::read_from(SAVEPATH)
+            .and_then(|snapshot| scenes::Game::load(&mut self.ecs, &snapshot))
+            .unwrap_or_else(|error| {
+                log::warn!("Failed to continue the game, starting a new one: {error}");
+                scenes::Game::create(&mut self.ecs)
+            });
+
+        self.scenes.push(&mut self.ecs, Scene::Game(game));
+    }
+
+    fn load_previous_scene(&mut self) {
+        self.scenes.pop(&mut self.ecs);
+
+        if self.scenes.is_empty() {
+            self.load_main_menu();
+        }
+    }
+
+    // only a running game can be paused, e.g. not while the settings are open
+    fn pause_game(&mut self) {
+        let Some(Scene::Game(_)) = self.scenes.top() else {
+            return;
+        };
 
-        self.current_scene = Scene::Game(scenes::Game::create(&mut self.ecs));
+        // without a menu there would be no way to resume, the game keeps running
+        match PauseMenu::create(&mut self.ecs) {
+            Ok(pause_menu) => {
+                let pause_menu = Scene::Menu(Menu::PauseMenu(pause_menu));
+                self.scenes.push(&mut self.ecs, pause_menu);
+            }
+            Err(error) => log::error!("Failed to load the pause menu: {error}"),
+        }
+    }
+
+    fn resume_game(&mut self) {
+        if let Some(Scene::Menu(Menu::PauseMenu(_))) = self.scenes.top() {
+            self.scenes.pop(&mut self.ecs);
+        }
+    }
+
+    fn toggle_fullscreen(&mut self) {
+        let Some(config) = self.ecs.resource_mut::<Config>() else {
+            return;
+        };
+
+        config.fullscreen = !config.fullscreen;
+        self.file_config.fullscreen = config.fullscreen;
+        if let Some(window) = &self.window {
+            window.set_fullscreen(config.fullscreen);
+        }
+
+        self.config_changed();
+    }
+
+    fn cycle_fps(&mut self) {
+        let Some(config) = self.ecs.resource_mut::<Config>() else {
+            return;
+        };
+
+        config.fps = config.next_fps();
+        self.file_config.fps = config.fps;
+        self.frame_time = config.frame_time();
+
+        self.config_changed();
+    }
+
+    // the scene is switched once the old one is covered
+    fn start_transition(&mut self, event: GameEvent) {
+        // input is blocked while a transition plays, so there is no second switch to queue
+        if self.transition.is_some() {
+            return;
+        }
+
+        let kind = match event {
+            GameEvent::MainMenu => TransitionKind::Slide,
+            _ => TransitionKind::Fade,
+        };
+
+        self.transition = Some(Transition::start(kind, event));
+    }
+
+    fn switch_scene(&mut self, event: GameEvent) -> bool {
+        match event {
+            GameEvent::NewGame => self.load_new_game(),
+            GameEvent::ContinueGame => self.load_continue_game(),
+            GameEvent::MainMenu => self.load_main_menu(),
+            _ => log::error!("{event:?} does not switch scenes!"),
+        }
+
+        self.scene_ready()
     }
 }
 
 impl<API: RenderAPI> WindowEventHandler for Game<API> {
     fn redraw_requested(&mut self) {
+        let frame_time = self.stats.begin_frame();
         let start_time = Instant::now();
 
-        self.ecs
-            .process_inputs(&self.current_scene, &self.event_proxy);
+        if self.update_transition() {
+            self.ecs.discard_inputs();
+        } else {
+            self.ecs.process_inputs(&self.scenes, &self.event_proxy);
+        }
+        let input_time = start_time.elapsed();
+
+        let update_start = Instant::now();
+        self.ecs.update(&self.scenes.updated());
 
-        self.ecs.update_positions(&self.current_scene);
+        self.inspector.refresh(&mut self.ecs);
+        self.stats.refresh(&mut self.ecs);
+        crash::set_entity_count(self.ecs.entity_count());
+        let update_time = update_start.elapsed();
 
         let window = self
             .window
@@ -62,15 +211,32 @@ impl<API: RenderAPI> WindowEventHandler for Game<API> {
             .expect("Window was lost before rendering!");
 
         let minimized = window.is_minimized().unwrap_or(false);
-        if !minimized {
-            self.ecs
-                .render(&mut window.render_context, &self.current_scene);
+        let render_timings = if minimized {
+            RenderTimings::default()
+        } else {
+            let rendered = self.scenes.rendered();
+            let panels = [
+                self.inspector.panel(),
+                self.stats.panel(),
+                self.crash_notice.panel(),
+            ];
+            match PanelScene::create(&rendered, &panels) {
+                Some(scene) => self.ecs.render(&mut window.render_context, &scene),
+                None => self.ecs.render(&mut window.render_context, &rendered),
+            }
+        };
+
+        if let Some(frame_time) = frame_time {
+            self.stats.record(FrameSample::new(
+                frame_time,
+                input_time,
+                update_time,
+                render_timings,
+            ));
         }
 
         let render_time = Instant::elapsed(&start_time);
 
-        println!("{render_time:?}");
-
         let remaining_time = self.frame_time.saturating_sub(render_time);
         if !remaining_time.is_zero() {
             thread::sleep(remaining_time);
@@ -81,4 +247,36 @@ impl<API: RenderAPI> WindowEventHandler for Game<API> {
             .expect("Window was lost after rendering!")
             .request_render();
     }
+
+    // true while a transition plays, input is blocked until it is finished
+    fn update_transition(&mut self) -> bool {
+        let pending = self.transition.as_mut().and_then(Transition::take_pending);
+        let ready = match pending {
+            Some(event) => self.switch_scene(event),
+            // the screen stays covered while the next scene is loading
+            None => self.scene_ready(),
+        };
+
+        if ready {
+            if let Some(transition) = &mut self.transition {
+                transition.reveal();
+            }
+        }
+
+        if self
+            .transition
+            .as_ref()
+            .is_some_and(Transition::is_finished)
+        {
+            self.transition = None;
+        }
+
+        let screen_effect = self
+            .transition
+            .as_ref()
+            .map_or_else(ScreenEffect::default, Transition::screen_effect);
+        self.ecs.insert_resource(screen_effect);
+
+        self.transition.is_some()
+    }
 }
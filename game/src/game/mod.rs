@@ -2,12 +2,20 @@ mod app;
 mod event;
 
 use crate::{
-    constants::FPS,
-    scenes::{MainMenu, Menu, Scene},
+    args::{Args, StartScene},
+    config::Config,
+    constants::CONFIGPATH,
+    crash::{self, CrashNotice},
+    inspector::Inspector,
+    logging,
+    scenes::{Progress, SceneStack, SettingsMenu},
+    stats::FrameStats,
+    transition::{Transition, TransitionKind},
     Window,
 };
-use ecs::ECS;
-pub use event::GameEvent;
+use ecs::{Random, ResourceError, Time, ECS};
+use event::UserEventHandler;
+pub use event::{DebugKeyHandler, GameEvent};
 use rendering::RenderAPI;
 use std::{
     sync::{
@@ -24,33 +32,98 @@ pub struct Game<API: RenderAPI> {
     frame_time: Duration,
     ecs: ECS<GameEvent>,
     event_proxy: EventLoopProxy<GameEvent>,
-    current_scene: Scene,
+    scenes: SceneStack,
+    inspector: Inspector,
+    stats: FrameStats,
+    crash_notice: CrashNotice,
+    transition: Option<Transition>,
+    // what is saved, without the overrides of the command line
+    file_config: Config,
+    start_scene: StartScene,
+    validation: bool,
 }
 
 impl<API: RenderAPI> Game<API> {
-    #[must_use]
-    pub fn new(event_loop: &EventLoop<GameEvent>) -> Self {
-        Self {
+    pub fn new(event_loop: &EventLoop<GameEvent>, args: &Args) -> Result<Self, ResourceError> {
+        let file_config = Config::load(CONFIGPATH);
+        let mut config = file_config.clone();
+        args.apply(&mut config);
+        logging::configure(&config.log);
+        crash::set_config(&config);
+
+        let random = args.seed.map_or_else(Random::from_time, Random::new);
+        log::info!("Seed: {}", random.seed());
+
+        let mut ecs = ECS::create(&config.paths.texture_table, &config.paths.fonts)?;
+        ecs.register_saved_resource::<Progress>("progress");
+        ecs.insert_resource(Time::new(config.tick_time()));
+        let frame_time = config.frame_time();
+        ecs.insert_resource(config);
+        ecs.insert_resource(random);
+
+        Ok(Self {
             window: None,
             is_running: Arc::new(AtomicBool::new(true)),
-            frame_time: Duration::from_secs_f64(1.0 / f64::from(FPS)),
-            ecs: ECS::create("res/texture_table.json", "res/fonts"),
+            frame_time,
+            ecs,
             event_proxy: event_loop.create_proxy(),
-            current_scene: Scene::None,
-        }
+            scenes: SceneStack::default(),
+            inspector: Inspector::default(),
+            stats: FrameStats::default(),
+            crash_notice: CrashNotice::default(),
+            transition: None,
+            file_config,
+            start_scene: args.start_scene,
+            validation: args.validation_enabled(),
+        })
     }
 
     fn initialize(&mut self, event_loop: &ActiveEventLoop) {
         let texture_count = self.ecs.get_max_texture_count();
-        let window = Window::create(event_loop, texture_count);
+        let config = self
+            .ecs
+            .resource::<Config>()
+            .expect("Config was lost before initializing!");
+        let window = match Window::create(event_loop, texture_count, config, self.validation) {
+            Ok(window) => window,
+            Err(error) => {
+                log::error!("{error}");
+                event_loop.exit();
+                self.is_running.store(false, Ordering::Release);
+                return;
+            }
+        };
 
         self.ecs.initialize(&window.render_context);
 
-        self.current_scene = Scene::Menu(Menu::MainMenu(MainMenu::create(&mut self.ecs)));
+        match self.start_scene {
+            StartScene::MainMenu => self.load_main_menu(),
+            StartScene::NewGame => self.load_new_game(),
+            StartScene::ContinueGame => self.load_continue_game(),
+        }
+        self.transition = Some(Transition::reveal_current(TransitionKind::Fade));
+        self.crash_notice.show_pending(&mut self.ecs);
 
         self.window = Some(window);
     }
 
+    // options changed by the player are kept for the next start, command line flags are not
+    fn config_changed(&mut self) {
+        self.file_config.save(CONFIGPATH);
+
+        if let Some(config) = self.ecs.resource::<Config>() {
+            crash::set_config(config);
+        }
+
+        SettingsMenu::display_config(&mut self.ecs);
+    }
+
+    fn scene_ready(&self) -> bool {
+        self.scenes
+            .top()
+            .is_none_or(|scene| scene.is_ready(&self.ecs))
+    }
+
     fn exit(&mut self, event_loop: &ActiveEventLoop) {
         event_loop.exit();
         self.is_running.store(false, Ordering::Release);
@@ -62,6 +135,8 @@ impl<API: RenderAPI> Game<API> {
 
         window_ref.render_context.wait_idle();
 
+        self.scenes.save(&self.ecs);
+
         self.ecs.destroy();
 
         window_ref.render_context.destroy();
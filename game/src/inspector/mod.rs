@@ -0,0 +1,181 @@
+use crate::{
+    constants::INSPECTORDUMP,
+    game::DebugKeyHandler,
+    panel::{TextPanel, PANEL_TAG},
+    GameEvent,
+};
+
+use ecs::{Entity, FieldValue, InspectedEntity, TagComponent, ECS};
+use glam::Vec2;
+use std::fs;
+use winit::keyboard::{Key, NamedKey};
+
+const LINE_COUNT: usize = 20;
+const SMALL_STEP: f32 = 0.01;
+const LARGE_STEP: f32 = 1.0;
+
+// debug view of the ecs, toggled with F3, F4 dumps every entity to stdout and a file
+pub struct Inspector {
+    panel: TextPanel,
+    selected: Option<Entity>,
+    field: usize,
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self {
+            panel: TextPanel::new(Vec2 { x: -0.5, y: -0.95 }, 0.9),
+            selected: None,
+            field: 0,
+        }
+    }
+}
+
+impl Inspector {
+    pub fn panel(&self) -> &TextPanel {
+        &self.panel
+    }
+
+    fn dump(ecs: &ECS<GameEvent>) {
+        let dump: String = inspect_world(ecs).iter().map(ToString::to_string).collect();
+
+        log::info!("{dump}");
+        if let Err(error) = fs::write(INSPECTORDUMP, dump) {
+            log::error!("Failed to write inspector dump: {error}");
+        }
+    }
+
+    fn select_entity(&mut self, ecs: &ECS<GameEvent>, previous: bool) {
+        let world = inspect_world(ecs);
+        if world.is_empty() {
+            return;
+        }
+
+        let current = self
+            .selected
+            .and_then(|selected| world.iter().position(|entry| entry.entity == selected))
+            .unwrap_or(0);
+        let next = if previous {
+            (current + world.len() - 1) % world.len()
+        } else {
+            (current + 1) % world.len()
+        };
+
+        self.selected = Some(world[next].entity);
+        self.field = 0;
+    }
+
+    fn edit_selected(&mut self, ecs: &mut ECS<GameEvent>, step: f32) {
+        let Some(inspected) = self.selected.and_then(|entity| ecs.inspect_entity(entity)) else {
+            return;
+        };
+
+        let Some((key, field, FieldValue::Number(value))) = fields(&inspected).nth(self.field)
+        else {
+            return;
+        };
+
+        ecs.edit_field(inspected.entity, key, field, value + step);
+    }
+
+    // keeps the lines in sync with the selected entity, called once per frame
+    pub fn refresh(&mut self, ecs: &mut ECS<GameEvent>) {
+        if !self.panel.is_open() {
+            return;
+        }
+
+        // falls back to the first entity if the selected one is gone, e.g. after a scene change
+        if !self.selected.is_some_and(|selected| ecs.is_alive(selected)) {
+            self.selected = inspect_world(ecs).first().map(|entry| entry.entity);
+            self.field = 0;
+        }
+
+        let Some(inspected) = self.selected.and_then(|entity| ecs.inspect_entity(entity)) else {
+            return;
+        };
+
+        let texts = self.texts(&inspected);
+        self.panel.set_lines(ecs, texts);
+    }
+
+    fn texts(&mut self, inspected: &InspectedEntity) -> Vec<String> {
+        let header = match &inspected.name {
+            Some(name) => format!("entity {} {name}", inspected.entity),
+            None => format!("entity {}", inspected.entity),
+        };
+
+        let field_count = fields(inspected).count();
+        self.field = self.field.min(field_count.saturating_sub(1));
+
+        // scrolls so that the selected field stays visible below the header
+        let first = (self.field + 2).saturating_sub(LINE_COUNT);
+
+        let mut texts = vec![header];
+        texts.extend(
+            fields(inspected)
+                .enumerate()
+                .skip(first)
+                .take(LINE_COUNT - 1)
+                .map(|(index, (key, field, value))| {
+                    let marker = if index == self.field { ">" } else { " " };
+                    format!("{marker} {key}.{field}: {value}")
+                }),
+        );
+
+        texts
+    }
+}
+
+impl DebugKeyHandler for Inspector {
+    fn handle_key(&mut self, ecs: &mut ECS<GameEvent>, key: &Key) -> bool {
+        match key {
+            Key::Named(NamedKey::F3) => {
+                self.panel.toggle(ecs);
+                return true;
+            }
+            Key::Named(NamedKey::F4) => {
+                Self::dump(ecs);
+                return true;
+            }
+            _ => (),
+        }
+
+        if !self.panel.is_open() {
+            return false;
+        }
+
+        match key {
+            Key::Named(NamedKey::PageDown) => self.select_entity(ecs, false),
+            Key::Named(NamedKey::PageUp) => self.select_entity(ecs, true),
+            Key::Named(NamedKey::ArrowDown) => self.field += 1,
+            Key::Named(NamedKey::ArrowUp) => self.field = self.field.saturating_sub(1),
+            Key::Named(NamedKey::ArrowRight) => self.edit_selected(ecs, SMALL_STEP),
+            Key::Named(NamedKey::ArrowLeft) => self.edit_selected(ecs, -SMALL_STEP),
+            Key::Character(character) if character == "+" => self.edit_selected(ecs, LARGE_STEP),
+            Key::Character(character) if character == "-" => self.edit_selected(ecs, -LARGE_STEP),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+// debug panels are not listed, including the inspector itself
+fn inspect_world(ecs: &ECS<GameEvent>) -> Vec<InspectedEntity> {
+    ecs.entities()
+        .filter(|entity| {
+            !ecs.get_component::<TagComponent>(*entity)
+                .is_some_and(|tags| tags.has(PANEL_TAG))
+        })
+        .filter_map(|entity| ecs.inspect_entity(entity))
+        .collect()
+}
+
+fn fields(inspected: &InspectedEntity) -> impl Iterator<Item = (&str, &str, &FieldValue)> {
+    inspected.components.iter().flat_map(|component| {
+        component
+            .fields
+            .iter()
+            .map(|(field, value)| (component.key.as_str(), *field, value))
+    })
+}
@@ -1,7 +1,18 @@
+mod args;
+mod config;
 mod constants;
+mod crash;
 mod game;
+mod inspector;
+mod logging;
+mod panel;
 mod scenes;
+mod stats;
+mod transition;
 mod window;
 
+pub use args::{Args, USAGE};
+pub use crash::install_hook as install_crash_hook;
 pub use game::{Game, GameEvent};
+pub use logging::init as init_logging;
 use window::Window;
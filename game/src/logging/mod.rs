@@ -0,0 +1,295 @@
+use crate::{game::DebugKeyHandler, GameEvent};
+
+use ecs::ECS;
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock, RwLock},
+    time::Instant,
+};
+use winit::keyboard::{Key, NamedKey};
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+// kept in memory for crash reports
+const RECENT_LINE_COUNT: usize = 100;
+// F6 cycles through these at runtime
+const LEVELS: [LevelFilter; 5] = [
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LogConfig {
+    pub level: LevelFilter,
+    // levels of single modules, e.g. "ecs::system" or "vulkan", the longest match wins
+    pub targets: BTreeMap<String, LevelFilter>,
+    // nothing is written to a file without a path
+    pub file: Option<String>,
+    // bytes, the file is rotated once it would grow past this
+    pub max_file_size: u64,
+    // rotated files that are kept next to the current one
+    pub max_files: usize,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Info,
+            targets: BTreeMap::new(),
+            file: None,
+            max_file_size: 5 * 1024 * 1024,
+            max_files: 3,
+        }
+    }
+}
+
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+}
+
+impl LogFile {
+    fn open(path: &Path, max_size: u64, max_files: usize) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path: path.to_owned(),
+            file,
+            size,
+            max_size,
+            max_files,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let length = line.len() as u64 + 1;
+        if self.size > 0 && self.size + length > self.max_size {
+            self.rotate()?;
+        }
+
+        writeln!(self.file, "{line}")?;
+        self.size += length;
+
+        Ok(())
+    }
+
+    // game.log becomes game.log.1, game.log.1 becomes game.log.2 and so on, the oldest is dropped
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.max_files == 0 {
+            self.file = File::create(&self.path)?;
+            self.size = 0;
+            return Ok(());
+        }
+
+        let _ = fs::remove_file(self.rotated(self.max_files));
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated(index);
+            if from.exists() {
+                fs::rename(from, self.rotated(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+}
+
+struct Settings {
+    level: LevelFilter,
+    targets: BTreeMap<String, LevelFilter>,
+    file: Option<LogFile>,
+}
+
+impl Settings {
+    fn level_of(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .filter(|(prefix, _)| is_within(target, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.level, |(_, level)| *level)
+    }
+
+    // the macros skip everything above this without formatting it
+    fn max_level(&self) -> LevelFilter {
+        self.targets.values().copied().fold(self.level, Ord::max)
+    }
+}
+
+struct Logger {
+    started: Instant,
+    settings: RwLock<Settings>,
+    recent: Mutex<VecDeque<String>>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.settings
+            .read()
+            .is_ok_and(|settings| metadata.level() <= settings.level_of(metadata.target()))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "[{:>9.3} {:<5} {}] {}",
+            self.started.elapsed().as_secs_f64(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        println!("{line}");
+
+        if let Ok(mut recent) = self.recent.lock() {
+            if recent.len() == RECENT_LINE_COUNT {
+                recent.pop_front();
+            }
+            recent.push_back(line.clone());
+        }
+
+        let Ok(mut settings) = self.settings.write() else {
+            return;
+        };
+        if let Some(file) = &mut settings.file {
+            // there is nowhere left to log this to
+            let _ = file.write_line(&line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut settings) = self.settings.write() {
+            if let Some(log_file) = &mut settings.file {
+                let _ = log_file.file.flush();
+            }
+        }
+    }
+}
+
+// logs to stdout with the default level until the config is loaded
+pub fn init() {
+    let logger = LOGGER.get_or_init(|| Logger {
+        started: Instant::now(),
+        settings: RwLock::new(Settings {
+            level: LogConfig::default().level,
+            targets: BTreeMap::new(),
+            file: None,
+        }),
+        recent: Mutex::new(VecDeque::with_capacity(RECENT_LINE_COUNT)),
+    });
+
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(LogConfig::default().level);
+    }
+}
+
+pub fn configure(config: &LogConfig) {
+    let Some(logger) = LOGGER.get() else {
+        return;
+    };
+
+    let file = config.file.as_ref().and_then(|path| {
+        LogFile::open(Path::new(path), config.max_file_size, config.max_files)
+            .inspect_err(|error| log::error!("Failed to open log file {path}: {error}"))
+            .ok()
+    });
+
+    let Ok(mut settings) = logger.settings.write() else {
+        return;
+    };
+    *settings = Settings {
+        level: config.level,
+        targets: config.targets.clone(),
+        file,
+    };
+    log::set_max_level(settings.max_level());
+}
+
+pub fn set_level(level: LevelFilter) {
+    let Some(logger) = LOGGER.get() else {
+        return;
+    };
+
+    let Ok(mut settings) = logger.settings.write() else {
+        return;
+    };
+    settings.level = level;
+    log::set_max_level(settings.max_level());
+}
+
+pub fn level() -> LevelFilter {
+    LOGGER
+        .get()
+        .and_then(|logger| logger.settings.read().ok().map(|settings| settings.level))
+        .unwrap_or(LevelFilter::Off)
+}
+
+// does not wait for the lock, the panic might have happened while logging
+pub fn recent_lines() -> Vec<String> {
+    LOGGER
+        .get()
+        .and_then(|logger| logger.recent.try_lock().ok())
+        .map(|recent| recent.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+// F6 cycles through the log levels
+pub struct LevelKey;
+
+impl DebugKeyHandler for LevelKey {
+    fn handle_key(&mut self, _ecs: &mut ECS<GameEvent>, key: &Key) -> bool {
+        if *key != Key::Named(NamedKey::F6) {
+            return false;
+        }
+
+        let next = LEVELS
+            .iter()
+            .position(|level| *level == self::level())
+            .map_or(LEVELS[0], |index| LEVELS[(index + 1) % LEVELS.len()]);
+        set_level(next);
+
+        // logged at the new level itself, so it is always shown
+        if let Some(level) = next.to_level() {
+            log::log!(level, "Log level set to {next}");
+        }
+
+        true
+    }
+}
+
+// "ecs::system" covers "ecs::system::render" but not "ecs::systems"
+fn is_within(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
@@ -1,27 +1,42 @@
-use game::{Game, GameEvent};
+use game::{init_logging, install_crash_hook, Args, Game, GameEvent, USAGE};
 use rendering::VulkanAPI;
-use std::panic;
+use std::{env, process};
 use winit::event_loop::{ControlFlow, EventLoop};
 
 fn main() {
-    panic::set_hook(Box::new(|panic_info| {
-        if let Some(payload) = panic_info.payload().downcast_ref::<&'static str>() {
-            println!("{payload}");
-        } else if let Some(payload) = panic_info.payload().downcast_ref::<String>() {
-            println!("{payload}");
-        }
+    init_logging();
 
-        if let Some(panic_location) = panic_info.location() {
-            println!("{panic_location}");
-        }
-    }));
+    let args = Args::parse(env::args().skip(1)).unwrap_or_else(|error| {
+        println!("{error}\n\n{USAGE}");
+        process::exit(2);
+    });
+    if args.help {
+        println!("{USAGE}");
+        return;
+    }
+
+    // every relative path is resolved from here, including the ones in the texture table
+    if let Some(resource_dir) = &args.resource_dir {
+        env::set_current_dir(resource_dir).unwrap_or_else(|error| {
+            println!(
+                "Failed to use {} as resource dir: {error}",
+                resource_dir.display()
+            );
+            process::exit(2);
+        });
+    }
+
+    install_crash_hook();
 
     let event_loop = EventLoop::<GameEvent>::with_user_event()
         .build()
         .expect("Failed to build custom event loop!");
     event_loop.set_control_flow(ControlFlow::Poll);
 
-    let mut game = Game::<VulkanAPI>::new(&event_loop);
+    let mut game = Game::<VulkanAPI>::new(&event_loop, &args).unwrap_or_else(|error| {
+        log::error!("Failed to load resources: {error}");
+        process::exit(1);
+    });
 
     event_loop.run_app(&mut game).expect("Failed to run game!");
 }
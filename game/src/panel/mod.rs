@@ -0,0 +1,134 @@
+use crate::GameEvent;
+
+use ecs::{Entity, EntityProvider, TextComponent, TextContent, ECS};
+use glam::Vec2;
+use objects::{Content, Factory};
+
+const LINE_HEIGHT: f32 = 0.08;
+// debug panels are not part of the game, the inspector skips entities with this tag
+pub const PANEL_TAG: &str = "debug_panel";
+
+// lines of text drawn on top of the scene, e.g. the inspector or the frame stats
+pub struct TextPanel {
+    // groups the lines, only exists while the panel is open
+    root: Option<Entity>,
+    lines: Vec<Entity>,
+    // center of the first line
+    origin: Vec2,
+    width: f32,
+}
+
+impl TextPanel {
+    pub fn new(origin: Vec2, width: f32) -> Self {
+        Self {
+            root: None,
+            lines: Vec::new(),
+            origin,
+            width,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.root.is_some()
+    }
+
+    pub fn toggle(&mut self, ecs: &mut ECS<GameEvent>) {
+        if let Some(root) = self.root.take() {
+            // destroys the lines as well
+            ecs.destroy_entity(root);
+            self.lines.clear();
+            return;
+        }
+
+        let root = ecs.create_entity();
+        ecs.add_tag(root, PANEL_TAG);
+        self.root = Some(root);
+    }
+
+    // creates or destroys lines as needed, unchanged lines are not rasterized again
+    pub fn set_lines(&mut self, ecs: &mut ECS<GameEvent>, texts: Vec<String>) {
+        let Some(root) = self.root else {
+            return;
+        };
+
+        while self.lines.len() > texts.len() {
+            if let Some(line) = self.lines.pop() {
+                ecs.destroy_entity(line);
+            }
+        }
+
+        for (index, text) in texts.into_iter().enumerate() {
+            let Some(line) = self.lines.get(index).copied() else {
+                let line = self.create_line(ecs, index, text);
+                ecs.add_tag(line, PANEL_TAG);
+                ecs.set_parent(line, root);
+                self.lines.push(line);
+                continue;
+            };
+
+            let unchanged = ecs
+                .get_component::<TextComponent>(line)
+                .is_none_or(|component| component.content.text == text);
+            if unchanged {
+                continue;
+            }
+
+            if let Some(component) = ecs.get_component_mut::<TextComponent>(line) {
+                component.content.text = text;
+            }
+        }
+    }
+
+    fn create_line(&self, ecs: &mut ECS<GameEvent>, index: usize, text: String) -> Entity {
+        Factory::label(
+            ecs,
+            Vec2 {
+                x: self.origin.x,
+                y: self.origin.y + LINE_HEIGHT * index as f32,
+            },
+            Vec2 {
+                x: self.width,
+                y: LINE_HEIGHT,
+            },
+            Content::Text(TextContent {
+                text,
+                font: "test".to_owned(),
+                font_size: 30.0,
+            }),
+        )
+    }
+}
+
+// the scene with all open panels on top
+pub struct PanelScene {
+    entities: Vec<Entity>,
+    player: Option<Entity>,
+}
+
+impl PanelScene {
+    // None while every panel is closed, the scene can be rendered directly then
+    pub fn create(scene: &dyn EntityProvider, panels: &[&TextPanel]) -> Option<Self> {
+        let roots: Vec<Entity> = panels.iter().filter_map(|panel| panel.root).collect();
+        if roots.is_empty() {
+            return None;
+        }
+
+        let mut entities = scene.get_entities().to_vec();
+        entities.extend(roots);
+
+        Some(Self {
+            entities,
+            player: scene.get_player(),
+        })
+    }
+}
+
+impl EntityProvider for PanelScene {
+    fn get_entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    fn get_player(&self) -> Option<Entity> {
+        self.player
+    }
+}
@@ -0,0 +1,87 @@
+use crate::GameEvent;
+
+use objects::Actions;
+use winit::event_loop::EventLoopProxy;
+
+// callbacks that scene files can refer to by name
+pub fn actions() -> Actions<GameEvent> {
+    let mut actions: Actions<GameEvent> = Actions::with_capacity(11);
+
+    actions.insert("new_game", new_game_fn);
+    actions.insert("continue", continue_fn);
+    actions.insert("settings", settings_fn);
+    actions.insert("toggle_fullscreen", toggle_fullscreen_fn);
+    actions.insert("cycle_fps", cycle_fps_fn);
+    actions.insert("exit", exit_fn);
+    actions.insert("main_menu", main_menu_fn);
+    actions.insert("back", back_fn);
+    actions.insert("pause", pause_clicked);
+    actions.insert("resume", resume_fn);
+    actions.insert("open_inventory", open_inventory);
+
+    actions
+}
+
+fn continue_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::ContinueGame)
+        .expect("Failed to send continue event!");
+}
+
+fn new_game_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::NewGame)
+        .expect("Failed to send new game event!");
+}
+
+fn settings_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::SettingsMenu)
+        .expect("Failed to send settings event!");
+}
+
+fn toggle_fullscreen_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::ToggleFullscreen)
+        .expect("Failed to send toggle fullscreen event!");
+}
+
+fn cycle_fps_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::CycleFps)
+        .expect("Failed to send cycle fps event!");
+}
+
+fn exit_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::ExitGame)
+        .expect("Failed to send exit event!");
+}
+
+fn main_menu_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::MainMenu)
+        .expect("Failed to send MainMenu by pressing back button in settings!");
+}
+
+fn back_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::Back)
+        .expect("Failed to send back event!");
+}
+
+fn pause_clicked(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::Pause)
+        .expect("Failed to send pause event!");
+}
+
+fn resume_fn(event_proxy: &EventLoopProxy<GameEvent>) {
+    event_proxy
+        .send_event(GameEvent::Resume)
+        .expect("Failed to send resume event!");
+}
+
+fn open_inventory(_event_proxy: &EventLoopProxy<GameEvent>) {
+    // TODO inventory
+}
@@ -1,66 +1,154 @@
 mod overlay;
+mod progress;
 
 use crate::game::GameEvent;
 
-use ecs::{Entity, InputHandler, MouseEvent, ECS};
+use ecs::{
+    Entity, EntityProvider, InputComponent, InputHandler, MouseEvent, Snapshot, SnapshotError, ECS,
+};
 use indexmap::IndexSet;
-use overlay::Overlay;
-use winit::{event_loop::EventLoopProxy, keyboard::Key};
+use overlay::{Overlay, ProgressDisplay};
+use winit::{
+    event::MouseButton,
+    event_loop::EventLoopProxy,
+    keyboard::{Key, NamedKey},
+};
+
+pub use progress::Progress;
 
 pub struct Game {
     pub player_id: Entity,
     pub objects: Vec<Entity>,
+    // everything that is saved, i.e. all objects except the overlay
+    world: Vec<Entity>,
 }
 
 impl Game {
     pub fn create(ecs: &mut ECS<GameEvent>) -> Self {
+        let progress = Progress::new_game(ecs.resource::<Progress>());
+        ecs.insert_resource(progress);
+
+        // objects.push(Player::create(ecs));
+        let player = ecs.create_entity();
+
+        Self::build(ecs, player, Vec::new())
+    }
+
+    // restores progress, player and world of a saved game
+    pub fn load(ecs: &mut ECS<GameEvent>, snapshot: &Snapshot) -> Result<Self, SnapshotError> {
+        let loaded = ecs.load_snapshot(snapshot)?;
+
+        let player = loaded.player.unwrap_or_else(|| ecs.create_entity());
+        let world = loaded
+            .roots
+            .into_iter()
+            .filter(|entity| *entity != player)
+            .collect();
+
+        Ok(Self::build(ecs, player, world))
+    }
+
+    fn build(ecs: &mut ECS<GameEvent>, player: Entity, world: Vec<Entity>) -> Self {
         let mut objects = Vec::with_capacity(100);
 
-        objects.extend(Overlay::create(ecs).objects);
+        // the game can be played without it
+        match Overlay::create(ecs) {
+            Ok(overlay) => objects.extend(overlay.objects),
+            Err(error) => log::error!("Failed to load the overlay, playing without it: {error}"),
+        }
 
         // TODO background
 
-        // objects.push(Player::create(ecs));
-        let player = ecs.create_entity();
-
         // TODO spawner
 
+        objects.extend(&world);
+
         Self {
             player_id: player,
             objects,
+            world,
         }
     }
 
+    pub fn save(&self, ecs: &ECS<GameEvent>) -> Result<Snapshot, SnapshotError> {
+        let mut entities = self.world.clone();
+        entities.push(self.player_id);
+
+        ecs.save_snapshot(&SavedWorld {
+            entities,
+            player: self.player_id,
+        })
+    }
+
     pub fn get_objects(&self) -> &[Entity] {
         &self.objects
     }
 
     pub fn destroy(&self, ecs: &mut ECS<GameEvent>) {
+        ecs.remove_system::<ProgressDisplay>();
+
         for obj in &self.objects {
             ecs.destroy_entity(*obj);
         }
+        ecs.destroy_entity(self.player_id);
+    }
+}
+
+struct SavedWorld {
+    entities: Vec<Entity>,
+    player: Entity,
+}
+
+impl EntityProvider for SavedWorld {
+    fn get_entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    fn get_player(&self) -> Option<Entity> {
+        Some(self.player)
     }
 }
 
 impl InputHandler<GameEvent> for Game {
     fn handle_mouse_events(
         &self,
-        _ecs: &ECS<GameEvent>,
+        ecs: &ECS<GameEvent>,
         events: &[MouseEvent],
-        _event_proxy: &EventLoopProxy<GameEvent>,
+        event_proxy: &EventLoopProxy<GameEvent>,
     ) {
-        // TODO player movement
+        for event in events {
+            if event.button.mouse_button != MouseButton::Left {
+                continue;
+            }
+
+            for entity in &self.objects {
+                if ecs
+                    .get_component::<InputComponent<GameEvent>>(*entity)
+                    .is_some()
+                    && ecs.position_matches_entity(&event.position, *entity)
+                {
+                    return ecs.activate_entity(entity, event_proxy);
+                }
+            }
 
-        // TODO skills / movement?
-        for _event in events {}
+            // TODO player movement
+
+            // TODO skills / movement?
+        }
     }
 
     fn handle_key_events(
         &self,
         _ecs: &mut ECS<GameEvent>,
         pressed_keys: &IndexSet<Key>,
-        _event_proxy: &EventLoopProxy<GameEvent>,
+        event_proxy: &EventLoopProxy<GameEvent>,
     ) {
+        if pressed_keys.contains(&Key::Named(NamedKey::Escape)) {
+            event_proxy
+                .send_event(GameEvent::Pause)
+                .expect("Failed to send Pause by pressing escape!");
+        }
+
         // TODO player movement
 
         // TODO skills
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+// progress of the running game, stored as an ecs resource
+#[derive(Default, Deserialize, Serialize)]
+pub struct Progress {
+    pub wave: u32,
+    pub money: u32,
+    // kept when a new game is started
+    pub highscore: u32,
+}
+
+impl Progress {
+    pub fn new_game(previous: Option<&Self>) -> Self {
+        Self {
+            highscore: previous.map_or(0, |progress| progress.highscore),
+            ..Self::default()
+        }
+    }
+
+    pub fn money_text(&self) -> String {
+        self.money.to_string()
+    }
+
+    pub fn wave_text(&self) -> String {
+        format!("Wave {}", self.wave)
+    }
+
+    pub fn highscore_text(&self) -> String {
+        format!("Highscore {}", self.highscore)
+    }
+}
@@ -1,115 +1,42 @@
-use crate::{scenes::Menu, GameEvent};
+use crate::{constants::SAVEPATH, scenes::spawn_scene, GameEvent};
 
-use ecs::{Entity, Layer, TextContent, ECS};
-use glam::Vec2;
-use objects::{Content, Factory};
-use winit::event_loop::EventLoopProxy;
-
-impl Menu {
-    fn create_banner(ecs: &mut ECS<GameEvent>) -> Entity {
-        Factory::label(
-            ecs,
-            Vec2 { x: 0.0, y: 0.5 },
-            Vec2 { x: 1.5, y: 0.5 },
-            Content::Image {
-                name: "main_menu_banner",
-                layer: Layer::Background,
-            },
-        )
-    }
-
-    fn create_new_game_button(ecs: &mut ECS<GameEvent>) -> Entity {
-        Factory::button(
-            ecs,
-            Vec2 { x: -0.5, y: 0.0 },
-            Vec2 { x: 0.5, y: 0.5 },
-            Content::Text(TextContent {
-                text: "New Game".to_owned(),
-                font: "test".to_owned(), // TODO adjust font
-                font_size: 50.0,         // TODO adjust font size
-            }),
-            true,
-            new_game_fn,
-        )
-    }
-
-    fn create_settings_button(ecs: &mut ECS<GameEvent>) -> Entity {
-        Factory::button(
-            ecs,
-            Vec2 { x: 0.0, y: 0.0 },
-            Vec2 { x: 0.5, y: 0.5 },
-            Content::Text(TextContent {
-                text: "Settings".to_owned(),
-                font: "test".to_owned(), // TODO adjust font
-                font_size: 50.0,         // TODO adjust font size
-            }),
-            false,
-            settings_fn,
-        )
-    }
-
-    fn create_exit_button(ecs: &mut ECS<GameEvent>) -> Entity {
-        Factory::button(
-            ecs,
-            Vec2 { x: 0.5, y: 0.0 },
-            Vec2 { x: 0.5, y: 0.5 },
-            Content::Text(TextContent {
-                text: "Exit".to_owned(),
-                font: "test".to_owned(), // TODO adjust font
-                font_size: 50.0,         // TODO adjust font size
-            }),
-            false,
-            exit_fn,
-        )
-    }
-}
+use ecs::{Entity, InputComponent, ECS};
+use objects::PrefabError;
+use std::path::Path;
 
 pub struct MainMenu {
     pub objects: Vec<Entity>,
 }
 
 impl MainMenu {
-    pub fn create(ecs: &mut ECS<GameEvent>) -> Self {
-        let mut objects: Vec<Entity> = Vec::with_capacity(6);
+    pub fn create(ecs: &mut ECS<GameEvent>) -> Result<Self, PrefabError> {
+        let mut objects = spawn_scene(ecs, |paths| &paths.main_menu)?;
 
-        objects.push(Menu::create_background(ecs));
-        objects.push(Menu::create_title(ecs));
-        objects.push(Menu::create_banner(ecs));
+        // continuing is only possible after a game was saved
+        if !Path::new(SAVEPATH).exists() {
+            if let Some(continue_button) = MainMenu::remove_continue_button(ecs) {
+                objects.retain(|entity| *entity != continue_button);
+            }
+        }
 
-        let new_game = Menu::create_new_game_button(ecs);
-        let settings = Menu::create_settings_button(ecs);
-        let exit = Menu::create_exit_button(ecs);
-
-        ecs.set_next_of(new_game, settings);
-        ecs.set_next_of(settings, exit);
-        ecs.set_next_of(exit, new_game);
-
-        ecs.set_previous_of(new_game, exit);
-        ecs.set_previous_of(settings, new_game);
-        ecs.set_previous_of(exit, settings);
+        Ok(Self { objects })
+    }
 
-        objects.push(new_game);
-        objects.push(settings);
-        objects.push(exit);
+    // new game takes the focus and becomes the first button of the focus chain
+    fn remove_continue_button(ecs: &mut ECS<GameEvent>) -> Option<Entity> {
+        let continue_button = ecs.find_by_name("continue")?;
+        let new_game = ecs.find_by_name("new_game")?;
+        let exit = ecs.find_by_name("exit")?;
 
-        Self { objects }
-    }
-}
+        ecs.destroy_entity(continue_button);
 
-fn new_game_fn(event_proxy: &EventLoopProxy<GameEvent>) {
-    event_proxy
-        .send_event(GameEvent::NewGame)
-        .expect("Failed to send new game event!");
-}
+        if let Some(input) = ecs.get_component_mut::<InputComponent<GameEvent>>(new_game) {
+            input.is_active = true;
+        }
 
-fn settings_fn(event_proxy: &EventLoopProxy<GameEvent>) {
-    event_proxy
-        .send_event(GameEvent::SettingsMenu)
-        .expect("Failed to send settings event!");
-}
+        ecs.set_next_of(exit, new_game);
+        ecs.set_previous_of(new_game, exit);
 
-fn exit_fn(event_proxy: &EventLoopProxy<GameEvent>) {
-    event_proxy
-        .send_event(GameEvent::ExitGame)
-        .expect("Failed to send exit event!");
+        Some(continue_button)
+    }
 }
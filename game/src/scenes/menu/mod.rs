@@ -1,12 +1,12 @@
 mod main_menu;
+mod pause_menu;
 mod settings_menu;
 
+use super::navigate_buttons;
 use crate::GameEvent;
 
-use ecs::{Entity, InputHandler, Layer, MouseEvent, ECS};
-use glam::{Vec2, Vec3};
+use ecs::{Entity, InputHandler, MouseEvent, ECS};
 use indexmap::IndexSet;
-use objects::{Content, Factory};
 use winit::{
     event::MouseButton,
     event_loop::EventLoopProxy,
@@ -14,56 +14,27 @@ use winit::{
 };
 
 pub use main_menu::MainMenu;
+pub use pause_menu::PauseMenu;
 pub use settings_menu::SettingsMenu;
 
+#[allow(clippy::enum_variant_names)]
 pub enum Menu {
     MainMenu(MainMenu),
     SettingsMenu(SettingsMenu),
+    PauseMenu(PauseMenu),
 }
 
 impl Menu {
-    pub fn create_background(ecs: &mut ECS<GameEvent>) -> Entity {
-        Factory::label(
-            ecs,
-            Vec3 {
-                x: 0.0,
-                y: 0.0,
-                z: 0.1,
-            },
-            Vec2 { x: 2.0, y: 2.0 },
-            Content::Image {
-                name: "menu_background",
-                layer: Layer::Background,
-            },
-        )
-    }
-
-    pub fn create_title(ecs: &mut ECS<GameEvent>) -> Entity {
-        Factory::label(
-            ecs,
-            Vec2 { x: 0.0, y: -0.8 },
-            Vec2 { x: 1.5, y: 0.5 },
-            Content::Image {
-                name: "menu_title",
-                layer: Layer::Background,
-            },
-        )
-    }
-
     pub fn get_objects(&self) -> &[Entity] {
         match self {
             Menu::MainMenu(main_menu) => &main_menu.objects,
             Menu::SettingsMenu(settings_menu) => &settings_menu.objects,
+            Menu::PauseMenu(pause_menu) => &pause_menu.objects,
         }
     }
 
     pub fn destroy(&self, ecs: &mut ECS<GameEvent>) {
-        let objects = match self {
-            Menu::MainMenu(main_menu) => &main_menu.objects,
-            Menu::SettingsMenu(settings_menu) => &settings_menu.objects,
-        };
-
-        for obj in objects {
+        for obj in self.get_objects() {
             ecs.destroy_entity(*obj);
         }
     }
@@ -97,32 +68,16 @@ impl InputHandler<GameEvent> for Menu {
     ) {
         for key in pressed_keys {
             match key {
-                Key::Named(NamedKey::Tab | NamedKey::ArrowDown | NamedKey::ArrowRight) => {
-                    if let Some(currently_active) = ecs.get_active_entity() {
-                        ecs.set_next_active(*currently_active);
-                    }
-                }
-
-                Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowUp) => {
-                    if let Some(currently_active) = ecs.get_active_entity() {
-                        ecs.set_previous_active(*currently_active);
-                    }
-                }
-
-                Key::Named(NamedKey::Space | NamedKey::Enter) => {
-                    if let Some(active_entity) = ecs.get_active_entity() {
-                        ecs.activate_entity(active_entity, event_proxy);
-                    }
-                }
-
-                Key::Named(NamedKey::Escape) => {
-                    if let Menu::SettingsMenu(_) = self {
-                        event_proxy
-                            .send_event(GameEvent::MainMenu)
-                            .expect("Failed to send MainMenu by pressing escape!");
-                    }
-                }
-                _ => (),
+                Key::Named(NamedKey::Escape) => match self {
+                    Menu::MainMenu(_) => (),
+                    Menu::SettingsMenu(_) => event_proxy
+                        .send_event(GameEvent::Back)
+                        .expect("Failed to send Back by pressing escape!"),
+                    Menu::PauseMenu(_) => event_proxy
+                        .send_event(GameEvent::Resume)
+                        .expect("Failed to send Resume by pressing escape!"),
+                },
+                _ => navigate_buttons(ecs, self.get_objects(), key, event_proxy),
             }
         }
     }
@@ -0,0 +1,17 @@
+use crate::{scenes::spawn_scene, GameEvent};
+
+use ecs::{Entity, ECS};
+use objects::PrefabError;
+
+// shown on top of the frozen game
+pub struct PauseMenu {
+    pub objects: Vec<Entity>,
+}
+
+impl PauseMenu {
+    pub fn create(ecs: &mut ECS<GameEvent>) -> Result<Self, PrefabError> {
+        Ok(Self {
+            objects: spawn_scene(ecs, |paths| &paths.pause_menu)?,
+        })
+    }
+}
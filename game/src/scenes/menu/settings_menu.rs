@@ -1,45 +1,41 @@
-use crate::{scenes::Menu, GameEvent};
+use crate::{
+    config::Config,
+    scenes::{spawn_scene, update_text},
+    GameEvent,
+};
 
-use ecs::{Entity, TextContent, ECS};
-use glam::Vec2;
-use objects::{Content, Factory};
-use winit::event_loop::EventLoopProxy;
+use ecs::{Entity, ECS};
+use objects::PrefabError;
 
 pub struct SettingsMenu {
     pub objects: Vec<Entity>,
 }
 
-impl Menu {
-    fn create_back_button(ecs: &mut ECS<GameEvent>) -> Entity {
-        Factory::button(
-            ecs,
-            Vec2 { x: 0.0, y: 0.5 },
-            Vec2 { x: 0.5, y: 0.5 },
-            Content::Text(TextContent {
-                text: "Back".to_owned(),
-                font: "test".to_owned(), // TODO adjust font
-                font_size: 50.0,         // TODO adjust font size
-            }),
-            true,
-            back_fn,
-        )
+impl SettingsMenu {
+    pub fn create(ecs: &mut ECS<GameEvent>) -> Result<Self, PrefabError> {
+        let objects = spawn_scene(ecs, |paths| &paths.settings_menu)?;
+
+        // the scene only holds placeholders
+        SettingsMenu::display_config(ecs);
+
+        Ok(Self { objects })
     }
-}
 
-impl SettingsMenu {
-    pub fn create(ecs: &mut ECS<GameEvent>) -> Self {
-        Self {
-            objects: vec![
-                Menu::create_background(ecs),
-                Menu::create_title(ecs),
-                Menu::create_back_button(ecs),
-            ],
+    // called again whenever an option was changed
+    pub fn display_config(ecs: &mut ECS<GameEvent>) {
+        let Some(config) = ecs.resource::<Config>() else {
+            return;
+        };
+
+        let texts = [
+            ("fullscreen", config.fullscreen_text()),
+            ("fps", config.fps_text()),
+        ];
+
+        for (name, text) in texts {
+            if let Some(entity) = ecs.find_by_name(name) {
+                update_text(ecs, entity, text);
+            }
         }
     }
 }
-
-fn back_fn(event_proxy: &EventLoopProxy<GameEvent>) {
-    event_proxy
-        .send_event(GameEvent::MainMenu)
-        .expect("Failed to send MainMenu by pressing back button in settings!");
-}
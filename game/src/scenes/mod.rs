@@ -1,39 +1,166 @@
+mod actions;
 mod game;
 mod menu;
+mod stack;
 
-use crate::GameEvent;
+use crate::{
+    config::{Config, Paths},
+    constants::SAVEPATH,
+    GameEvent,
+};
 
-use ecs::{Entity, EntityProvider, InputHandler, MouseEvent, ECS};
+use ecs::{Entity, EntityProvider, InputComponent, InputHandler, MouseEvent, TextComponent, ECS};
 use indexmap::IndexSet;
-use winit::{event_loop::EventLoopProxy, keyboard::Key};
+use objects::{Prefab, PrefabError};
+use winit::{
+    event_loop::EventLoopProxy,
+    keyboard::{Key, NamedKey},
+};
 
-pub use game::Game;
-pub use menu::{MainMenu, Menu, SettingsMenu};
+pub use game::{Game, Progress};
+pub use menu::{MainMenu, Menu, PauseMenu, SettingsMenu};
+pub use stack::SceneStack;
 
 pub enum Scene {
-    None,
     Menu(Menu),
     Game(Game),
 }
 
+// how a scene treats the scenes below it in the stack
+#[derive(Clone, Copy, Default)]
+pub struct SceneFlags {
+    // the simulation only runs while every scene above the game lets it
+    pub update_below: bool,
+    pub render_below: bool,
+    pub input_below: bool,
+}
+
 impl Scene {
     pub fn get_objects(&self) -> &[Entity] {
         match self {
-            Scene::None => &[],
             Scene::Menu(menu) => menu.get_objects(),
             Scene::Game(game) => game.get_objects(),
         }
     }
 
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scene::Menu(Menu::MainMenu(_)) => "main menu",
+            Scene::Menu(Menu::SettingsMenu(_)) => "settings menu",
+            Scene::Menu(Menu::PauseMenu(_)) => "pause menu",
+            Scene::Game(_) => "game",
+        }
+    }
+
+    // transitions keep the screen covered until this is true, scenes that load in the
+    // background can use it to finish loading first
+    pub fn is_ready(&self, _ecs: &ECS<GameEvent>) -> bool {
+        match self {
+            Scene::Menu(_) | Scene::Game(_) => true,
+        }
+    }
+
+    pub fn flags(&self) -> SceneFlags {
+        match self {
+            // the frozen game stays visible behind the pause menu
+            Scene::Menu(Menu::PauseMenu(_)) => SceneFlags {
+                render_below: true,
+                ..SceneFlags::default()
+            },
+            Scene::Menu(_) | Scene::Game(_) => SceneFlags::default(),
+        }
+    }
+
+    // only a running game has something to continue from
+    pub fn save(&self, ecs: &ECS<GameEvent>) {
+        let Scene::Game(game) = self else {
+            return;
+        };
+
+        let saved = game
+            .save(ecs)
+            .and_then(|snapshot| snapshot.write_to(SAVEPATH));
+        if let Err(error) = saved {
+            log::error!("Failed to save the game: {error}");
+        }
+    }
+
     pub fn destroy(&self, ecs: &mut ECS<GameEvent>) {
+        self.save(ecs);
+
         match self {
-            Scene::None => (),
             Scene::Menu(menu) => menu.destroy(ecs),
             Scene::Game(game) => game.destroy(ecs),
         }
     }
 }
 
+// nothing is spawned if the scene file is broken, the caller decides how to go on
+fn spawn_scene<F>(ecs: &mut ECS<GameEvent>, scene: F) -> Result<Vec<Entity>, PrefabError>
+where
+    F: Fn(&Paths) -> &String,
+{
+    let path = scene(
+        &ecs.resource::<Config>()
+            .expect("Config was not inserted before spawning scenes!")
+            .paths,
+    )
+    .clone();
+
+    Prefab::read_from(&path).and_then(|prefab| prefab.spawn(ecs, &actions::actions()))
+}
+
+// only texts that changed are rasterized again
+fn update_text(ecs: &mut ECS<GameEvent>, entity: Entity, text: String) {
+    let unchanged = ecs
+        .get_component::<TextComponent>(entity)
+        .is_none_or(|component| component.content.text == text);
+    if unchanged {
+        return;
+    }
+
+    // writing marks the text as changed, it is rasterized again before the next draw
+    if let Some(component) = ecs.get_component_mut::<TextComponent>(entity) {
+        component.content.text = text;
+    }
+}
+
+// the focused button of a scene, scenes below keep their focus while another one is on top
+fn active_button(ecs: &ECS<GameEvent>, objects: &[Entity]) -> Option<Entity> {
+    objects.iter().copied().find(|entity| {
+        ecs.get_component::<InputComponent<GameEvent>>(*entity)
+            .is_some_and(|input| input.is_active)
+    })
+}
+
+// moves the focus along the button chain and activates the focused button
+fn navigate_buttons(
+    ecs: &mut ECS<GameEvent>,
+    objects: &[Entity],
+    key: &Key,
+    event_proxy: &EventLoopProxy<GameEvent>,
+) {
+    let Some(currently_active) = active_button(ecs, objects) else {
+        return;
+    };
+
+    match key {
+        Key::Named(NamedKey::Tab | NamedKey::ArrowDown | NamedKey::ArrowRight) => {
+            ecs.set_next_active(currently_active);
+        }
+
+        Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowUp) => {
+            ecs.set_previous_active(currently_active);
+        }
+
+        Key::Named(NamedKey::Space | NamedKey::Enter) => {
+            ecs.activate_entity(&currently_active, event_proxy);
+        }
+
+        _ => (),
+    }
+}
+
 impl EntityProvider for Scene {
     fn get_entities(&self) -> &[Entity] {
         self.get_objects()
@@ -41,7 +168,7 @@ impl EntityProvider for Scene {
 
     fn get_player(&self) -> Option<Entity> {
         match self {
-            Scene::None | Scene::Menu(_) => None,
+            Scene::Menu(_) => None,
             Scene::Game(game) => Some(game.player_id),
         }
     }
@@ -55,7 +182,6 @@ impl InputHandler<GameEvent> for Scene {
         event_proxy: &EventLoopProxy<GameEvent>,
     ) {
         match self {
-            Scene::None => panic!("Should not handle mouse events in None scene"),
             Scene::Menu(menu) => menu.handle_mouse_events(ecs, events, event_proxy),
             Scene::Game(game) => game.handle_mouse_events(ecs, events, event_proxy),
         }
@@ -68,7 +194,6 @@ impl InputHandler<GameEvent> for Scene {
         event_proxy: &EventLoopProxy<GameEvent>,
     ) {
         match self {
-            Scene::None => panic!("Should not handle key events in None scene"),
             Scene::Menu(menu) => {
                 menu.handle_key_events(ecs, pressed_keys, event_proxy);
             }
@@ -0,0 +1,140 @@
+use super::{Scene, SceneFlags};
+use crate::{crash, GameEvent};
+
+use ecs::{Entity, EntityProvider, InputHandler, MouseEvent, Time, ECS};
+use indexmap::IndexSet;
+use winit::{event_loop::EventLoopProxy, keyboard::Key};
+
+// scenes on top of each other, e.g. settings over the pause menu over the game
+#[derive(Default)]
+pub struct SceneStack {
+    scenes: Vec<Scene>,
+}
+
+impl SceneStack {
+    pub fn top(&self) -> Option<&Scene> {
+        self.scenes.last()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    pub fn push(&mut self, ecs: &mut ECS<GameEvent>, scene: Scene) {
+        self.scenes.push(scene);
+        self.changed(ecs);
+    }
+
+    // destroys the top scene, the one below continues where it was left
+    pub fn pop(&mut self, ecs: &mut ECS<GameEvent>) {
+        if let Some(scene) = self.scenes.pop() {
+            scene.destroy(ecs);
+        }
+
+        self.changed(ecs);
+    }
+
+    pub fn clear(&mut self, ecs: &mut ECS<GameEvent>) {
+        while let Some(scene) = self.scenes.pop() {
+            scene.destroy(ecs);
+        }
+
+        self.changed(ecs);
+    }
+
+    pub fn save(&self, ecs: &ECS<GameEvent>) {
+        for scene in &self.scenes {
+            scene.save(ecs);
+        }
+    }
+
+    pub fn rendered(&self) -> SceneView {
+        SceneView::create(self.reachable(|flags| flags.render_below))
+    }
+
+    pub fn updated(&self) -> SceneView {
+        SceneView::create(self.reachable(|flags| flags.update_below))
+    }
+
+    // the top scene and every scene below it until one does not pass the flag on
+    fn reachable<F>(&self, passes: F) -> &[Scene]
+    where
+        F: Fn(SceneFlags) -> bool,
+    {
+        let first = self
+            .scenes
+            .iter()
+            .rposition(|scene| !passes(scene.flags()))
+            .unwrap_or(0);
+
+        &self.scenes[first..]
+    }
+
+    fn changed(&self, ecs: &mut ECS<GameEvent>) {
+        self.sync_time(ecs);
+
+        let names: Vec<&str> = self.scenes.iter().map(Scene::name).collect();
+        crash::set_scene(names.join(" > "));
+    }
+
+    // systems run on the whole ecs, so covered scenes are frozen by pausing the clock
+    fn sync_time(&self, ecs: &mut ECS<GameEvent>) {
+        let paused = self.reachable(|flags| flags.update_below).len() < self.scenes.len();
+
+        if let Some(time) = ecs.resource_mut::<Time>() {
+            time.set_paused(paused);
+        }
+    }
+}
+
+impl InputHandler<GameEvent> for SceneStack {
+    fn handle_mouse_events(
+        &self,
+        ecs: &ECS<GameEvent>,
+        events: &[MouseEvent],
+        event_proxy: &EventLoopProxy<GameEvent>,
+    ) {
+        for scene in self.reachable(|flags| flags.input_below).iter().rev() {
+            scene.handle_mouse_events(ecs, events, event_proxy);
+        }
+    }
+
+    fn handle_key_events(
+        &self,
+        ecs: &mut ECS<GameEvent>,
+        pressed_keys: &IndexSet<Key>,
+        event_proxy: &EventLoopProxy<GameEvent>,
+    ) {
+        for scene in self.reachable(|flags| flags.input_below).iter().rev() {
+            scene.handle_key_events(ecs, pressed_keys, event_proxy);
+        }
+    }
+}
+
+// entities of several scenes, from the bottom to the top
+pub struct SceneView {
+    entities: Vec<Entity>,
+    player: Option<Entity>,
+}
+
+impl SceneView {
+    fn create(scenes: &[Scene]) -> Self {
+        Self {
+            entities: scenes
+                .iter()
+                .flat_map(|scene| scene.get_objects().iter().copied())
+                .collect(),
+            player: scenes.iter().rev().find_map(EntityProvider::get_player),
+        }
+    }
+}
+
+impl EntityProvider for SceneView {
+    fn get_entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    fn get_player(&self) -> Option<Entity> {
+        self.player
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{constants::FRAMESTATSCSV, game::DebugKeyHandler, panel::TextPanel, GameEvent};
+
+use ecs::{RenderTimings, ECS};
+use glam::Vec2;
+use std::{
+    collections::VecDeque,
+    fmt::Write,
+    fs,
+    time::{Duration, Instant},
+};
+use winit::keyboard::{Key, NamedKey};
+
+// about 10 seconds at 60 fps
+const SAMPLE_COUNT: usize = 600;
+// the overlay is not rasterized again every frame
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+type Phase = fn(&FrameSample) -> Duration;
+
+#[derive(Clone, Copy, Default)]
+pub struct FrameSample {
+    // time since the previous frame started, including the sleep
+    pub frame: Duration,
+    pub input: Duration,
+    pub update: Duration,
+    pub render_prep: Duration,
+    pub gpu_submit: Duration,
+}
+
+impl FrameSample {
+    pub fn new(frame: Duration, input: Duration, update: Duration, render: RenderTimings) -> Self {
+        Self {
+            frame,
+            input,
+            update,
+            render_prep: render.prep,
+            gpu_submit: render.submit,
+        }
+    }
+
+    // time spent on work, without the sleep
+    fn busy(&self) -> Duration {
+        self.input + self.update + self.render_prep + self.gpu_submit
+    }
+}
+
+struct Summary {
+    average: Duration,
+    min: Duration,
+    max: Duration,
+    // average of the slowest 1% of frames
+    low: Duration,
+}
+
+impl Summary {
+    fn create<F>(samples: &VecDeque<FrameSample>, phase: F) -> Option<Self>
+    where
+        F: Fn(&FrameSample) -> Duration,
+    {
+        let mut durations: Vec<Duration> = samples.iter().map(phase).collect();
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort_unstable();
+
+        let count = u32::try_from(durations.len()).unwrap_or(u32::MAX);
+        let low_count = (durations.len() / 100).max(1);
+        let low_sum: Duration = durations.iter().rev().take(low_count).sum();
+
+        Some(Self {
+            average: durations.iter().sum::<Duration>() / count,
+            min: durations[0],
+            max: durations[durations.len() - 1],
+            low: low_sum / u32::try_from(low_count).unwrap_or(u32::MAX),
+        })
+    }
+}
+
+// timings of the last frames, F2 toggles the overlay, F5 exports them as csv
+pub struct FrameStats {
+    samples: VecDeque<FrameSample>,
+    // total number of frames, the first sample in the csv is not always frame 0
+    frame_count: u64,
+    last_frame: Option<Instant>,
+    last_refresh: Option<Instant>,
+    panel: TextPanel,
+}
+
+impl Default for FrameStats {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(SAMPLE_COUNT),
+            frame_count: 0,
+            last_frame: None,
+            last_refresh: None,
+            panel: TextPanel::new(Vec2 { x: 0.6, y: -0.95 }, 0.7),
+        }
+    }
+}
+
+impl FrameStats {
+    pub fn panel(&self) -> &TextPanel {
+        &self.panel
+    }
+
+    // marks the start of a frame and returns the time since the previous one,
+    // None for the first frame, which has nothing to be measured against
+    pub fn begin_frame(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let frame = self.last_frame.map(|last_frame| now - last_frame);
+        self.last_frame = Some(now);
+
+        frame
+    }
+
+    pub fn record(&mut self, sample: FrameSample) {
+        if self.samples.len() == SAMPLE_COUNT {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.frame_count += 1;
+    }
+
+    pub fn refresh(&mut self, ecs: &mut ECS<GameEvent>) {
+        if !self.panel.is_open() {
+            return;
+        }
+
+        if self
+            .last_refresh
+            .is_some_and(|last_refresh| last_refresh.elapsed() < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.last_refresh = Some(Instant::now());
+
+        let texts = self.texts();
+        self.panel.set_lines(ecs, texts);
+    }
+
+    fn texts(&self) -> Vec<String> {
+        let Some(frame) = Summary::create(&self.samples, |sample| sample.frame) else {
+            return vec!["no frames yet".to_owned()];
+        };
+
+        let fps = if frame.average.is_zero() {
+            0.0
+        } else {
+            1.0 / frame.average.as_secs_f64()
+        };
+
+        let mut texts = vec![
+            format!("fps {fps:.1}"),
+            format!("frame {}", format_summary(&frame)),
+        ];
+
+        let phases: [(&str, Phase); 5] = [
+            ("busy", FrameSample::busy),
+            ("input", |sample| sample.input),
+            ("update", |sample| sample.update),
+            ("prep", |sample| sample.render_prep),
+            ("submit", |sample| sample.gpu_submit),
+        ];
+        for (name, phase) in phases {
+            if let Some(summary) = Summary::create(&self.samples, phase) {
+                texts.push(format!("{name} {}", format_summary(&summary)));
+            }
+        }
+
+        texts
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = "frame,frame_ms,input_ms,update_ms,render_prep_ms,gpu_submit_ms\n".to_owned();
+
+        let first_frame = self.frame_count - self.samples.len() as u64;
+        for (frame, sample) in (first_frame..).zip(&self.samples) {
+            let _ = writeln!(
+                csv,
+                "{frame},{:.3},{:.3},{:.3},{:.3},{:.3}",
+                millis(sample.frame),
+                millis(sample.input),
+                millis(sample.update),
+                millis(sample.render_prep),
+                millis(sample.gpu_submit),
+            );
+        }
+
+        csv
+    }
+
+    fn export(&self) {
+        match fs::write(FRAMESTATSCSV, self.to_csv()) {
+            Ok(()) => log::info!("Exported {} frames to {FRAMESTATSCSV}", self.samples.len()),
+            Err(error) => log::error!("Failed to export frame stats: {error}"),
+        }
+    }
+}
+
+impl DebugKeyHandler for FrameStats {
+    fn handle_key(&mut self, ecs: &mut ECS<GameEvent>, key: &Key) -> bool {
+        match key {
+            Key::Named(NamedKey::F2) => {
+                self.panel.toggle(ecs);
+                self.last_refresh = None;
+            }
+            Key::Named(NamedKey::F5) => self.export(),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn format_summary(summary: &Summary) -> String {
+    format!(
+        "avg {:.2} min {:.2} max {:.2} 1% {:.2} ms",
+        millis(summary.average),
+        millis(summary.min),
+        millis(summary.max),
+        millis(summary.low),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameSample, FrameStats, Summary, SAMPLE_COUNT};
+
+    use std::time::Duration;
+
+    fn sample(millis: u64) -> FrameSample {
+        FrameSample {
+            frame: Duration::from_millis(millis),
+            input: Duration::from_millis(1),
+            ..FrameSample::default()
+        }
+    }
+
+    #[test]
+    fn the_first_frame_has_no_frame_time() {
+        let mut stats = FrameStats::default();
+
+        assert!(stats.begin_frame().is_none());
+        assert!(stats.begin_frame().is_some());
+    }
+
+    #[test]
+    fn summaries_include_the_slowest_percent() {
+        let mut stats = FrameStats::default();
+        for millis in (1..=200).rev() {
+            stats.record(sample(millis));
+        }
+
+        let summary =
+            Summary::create(&stats.samples, |sample| sample.frame).expect("No samples recorded!");
+        assert_eq!(summary.average, Duration::from_micros(100_500));
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.max, Duration::from_millis(200));
+        // 2 of 200 frames, 200 and 199 ms
+        assert_eq!(summary.low, Duration::from_micros(199_500));
+
+        let busy =
+            Summary::create(&stats.samples, FrameSample::busy).expect("No samples recorded!");
+        assert_eq!(busy.max, Duration::from_millis(1));
+
+        assert!(Summary::create(&FrameStats::default().samples, |sample| sample.frame).is_none());
+    }
+
+    #[test]
+    fn csv_rows_keep_counting_after_old_samples_are_dropped() {
+        let mut stats = FrameStats::default();
+        for millis in 0..SAMPLE_COUNT as u64 + 5 {
+            stats.record(sample(millis));
+        }
+
+        let csv = stats.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows.len(), SAMPLE_COUNT + 1);
+        assert_eq!(
+            rows[0],
+            "frame,frame_ms,input_ms,update_ms,render_prep_ms,gpu_submit_ms"
+        );
+        assert_eq!(rows[1], "5,5.000,1.000,0.000,0.000,0.000");
+        assert_eq!(
+            rows[SAMPLE_COUNT],
+            format!(
+                "{},{}.000,1.000,0.000,0.000,0.000",
+                SAMPLE_COUNT + 4,
+                SAMPLE_COUNT + 4
+            )
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use crate::GameEvent;
+
+use ecs::ScreenEffect;
+use glam::Vec2;
+use std::time::{Duration, Instant};
+
+// each half, covering the old scene and revealing the next one
+const DURATION: Duration = Duration::from_millis(300);
+
+#[derive(Clone, Copy)]
+pub enum TransitionKind {
+    Fade,
+    Slide,
+}
+
+#[derive(Clone, Copy)]
+enum Phase {
+    Out(Instant),
+    // the old scene is gone, lasts until the next one is ready
+    Covered,
+    In(Instant),
+}
+
+// plays between two scenes, input is blocked until it is finished
+pub struct Transition {
+    kind: TransitionKind,
+    phase: Phase,
+    // switches the scene once the screen is covered
+    pending: Option<GameEvent>,
+}
+
+impl Transition {
+    pub fn start(kind: TransitionKind, pending: GameEvent) -> Self {
+        Self {
+            kind,
+            phase: Phase::Out(Instant::now()),
+            pending: Some(pending),
+        }
+    }
+
+    // only reveals the current scene, e.g. after startup
+    pub fn reveal_current(kind: TransitionKind) -> Self {
+        Self {
+            kind,
+            phase: Phase::In(Instant::now()),
+            pending: None,
+        }
+    }
+
+    // the scene switch, available once the old scene is fully covered
+    pub fn take_pending(&mut self) -> Option<GameEvent> {
+        if let Phase::Out(started) = self.phase {
+            if started.elapsed() >= DURATION {
+                self.phase = Phase::Covered;
+            }
+        }
+
+        match self.phase {
+            Phase::Covered => self.pending.take(),
+            Phase::Out(_) | Phase::In(_) => None,
+        }
+    }
+
+    // the screen stays covered until the next scene is ready, no matter how long loading takes
+    pub fn reveal(&mut self) {
+        if let Phase::Covered = self.phase {
+            self.phase = Phase::In(Instant::now());
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase, Phase::In(started) if started.elapsed() >= DURATION)
+    }
+
+    pub fn screen_effect(&self) -> ScreenEffect {
+        let coverage = self.coverage();
+
+        match self.kind {
+            TransitionKind::Fade => ScreenEffect {
+                opacity: 1.0 - coverage,
+                offset: Vec2::ZERO,
+            },
+
+            // the old scene leaves to the left, the next one comes in from the right
+            TransitionKind::Slide => {
+                let direction = match self.phase {
+                    Phase::In(_) => 1.0,
+                    Phase::Out(_) | Phase::Covered => -1.0,
+                };
+
+                ScreenEffect {
+                    opacity: 1.0,
+                    offset: Vec2::new(direction * 2.0 * coverage, 0.0),
+                }
+            }
+        }
+    }
+
+    // 0 while the scene is fully visible, 1 while the screen is covered
+    fn coverage(&self) -> f32 {
+        match self.phase {
+            Phase::Out(started) => progress(started),
+            Phase::Covered => 1.0,
+            Phase::In(started) => 1.0 - progress(started),
+        }
+    }
+}
+
+fn progress(started: Instant) -> f32 {
+    (started.elapsed().as_secs_f32() / DURATION.as_secs_f32()).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Phase, Transition, TransitionKind, DURATION};
+    use crate::GameEvent;
+
+    use std::time::Instant;
+
+    fn covered() -> Transition {
+        let mut transition = Transition::start(TransitionKind::Fade, GameEvent::MainMenu);
+        transition.phase = Phase::Out(
+            Instant::now()
+                .checked_sub(DURATION)
+                .expect("Failed to go back in time!"),
+        );
+
+        transition
+    }
+
+    #[test]
+    fn the_switch_waits_for_the_screen_to_be_covered() {
+        let mut transition = Transition::start(TransitionKind::Fade, GameEvent::MainMenu);
+        assert!(transition.take_pending().is_none());
+
+        // too early, nothing happens
+        transition.reveal();
+        assert!(matches!(transition.phase, Phase::Out(_)));
+
+        let mut transition = covered();
+        assert!(matches!(
+            transition.take_pending(),
+            Some(GameEvent::MainMenu)
+        ));
+        assert!(transition.take_pending().is_none());
+    }
+
+    #[test]
+    fn the_screen_stays_covered_until_the_next_scene_is_ready() {
+        let mut transition = covered();
+        let _ = transition.take_pending();
+
+        for _ in 0..3 {
+            assert!(transition.screen_effect().opacity.abs() < f32::EPSILON);
+            assert!(!transition.is_finished());
+        }
+
+        transition.reveal();
+        assert!(matches!(transition.phase, Phase::In(_)));
+        assert!(!transition.is_finished());
+    }
+}
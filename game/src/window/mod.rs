@@ -1,10 +1,31 @@
 use rendering::{RenderAPI, RenderContext};
+use std::fmt::{self, Display, Formatter};
 use winit::{
+    error::OsError,
     event_loop::ActiveEventLoop,
-    window::{Fullscreen::Borderless, Icon},
+    window::{BadIcon, Fullscreen::Borderless, Icon},
 };
 
-use crate::constants::{FRAGSHADER, FRAMES_IN_FLIGHT, FULLSCREEN, ICONPATH, TITLE, VERTSHADER};
+use crate::config::Config;
+
+#[derive(Debug)]
+pub enum WindowError {
+    Image(image::ImageError),
+    Icon(BadIcon),
+    Os(OsError),
+}
+
+impl Display for WindowError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            WindowError::Image(error) => write!(formatter, "failed to open the icon: {error}"),
+            WindowError::Icon(error) => write!(formatter, "invalid icon: {error}"),
+            WindowError::Os(error) => write!(formatter, "failed to create the window: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for WindowError {}
 
 pub struct Window<API: RenderAPI> {
     inner_window: winit::window::Window,
@@ -12,51 +33,63 @@ pub struct Window<API: RenderAPI> {
 }
 
 impl<API: RenderAPI> Window<API> {
-    pub fn create(event_loop: &ActiveEventLoop, max_texture_count: u32) -> Self {
-        let (icon_rgba, icon_width, icon_height) = {
-            let image = image::open(ICONPATH)
-                .expect("Failed to open icon image!")
-                .into_rgba8();
-            let (width, height) = image.dimensions();
-            let rgba = image.into_raw();
-            (rgba, width, height)
-        };
-        let icon =
-            Icon::from_rgba(icon_rgba, icon_width, icon_height).expect("Failed to create icon!");
+    pub fn create(
+        event_loop: &ActiveEventLoop,
+        max_texture_count: u32,
+        config: &Config,
+        validation: bool,
+    ) -> Result<Self, WindowError> {
+        // the game still starts without an icon
+        let icon = load_icon(&config.paths.icon)
+            .inspect_err(|error| log::warn!("{error}, starting without an icon"))
+            .ok();
 
         let mut attributes = winit::window::Window::default_attributes()
-            .with_title(TITLE)
-            .with_window_icon(Some(icon))
+            .with_title(&config.title)
+            .with_window_icon(icon)
             .with_visible(false);
-        if FULLSCREEN {
+        if config.fullscreen {
             attributes = attributes.with_fullscreen(Some(Borderless(None)));
         }
 
         let inner_window = event_loop
             .create_window(attributes)
-            .expect("Failed to create inner window!");
+            .map_err(WindowError::Os)?;
 
         let render_context = RenderContext::create(
             &inner_window,
             max_texture_count,
-            TITLE,
-            FRAMES_IN_FLIGHT,
-            VERTSHADER,
-            FRAGSHADER,
+            &config.title,
+            config.frames_in_flight,
+            &config.paths.vertex_shader,
+            &config.paths.fragment_shader,
+            validation,
         );
 
         inner_window.set_visible(true);
-        Self {
+        Ok(Self {
             inner_window,
             render_context,
-        }
+        })
     }
 
     pub fn is_minimized(&self) -> Option<bool> {
         self.inner_window.is_minimized()
     }
 
+    pub fn set_fullscreen(&self, fullscreen: bool) {
+        self.inner_window
+            .set_fullscreen(fullscreen.then_some(Borderless(None)));
+    }
+
     pub fn request_render(&self) {
         self.inner_window.request_redraw();
     }
 }
+
+fn load_icon(path: &str) -> Result<Icon, WindowError> {
+    let image = image::open(path).map_err(WindowError::Image)?.into_rgba8();
+    let (width, height) = image.dimensions();
+
+    Icon::from_rgba(image.into_raw(), width, height).map_err(WindowError::Icon)
+}
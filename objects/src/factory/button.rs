@@ -1,8 +1,6 @@
 use super::{super::Content, Factory};
 
-use ecs::{
-    Component, Entity, InputComponent, PositionComponent, TextComponent, VisualComponent, ECS,
-};
+use ecs::{Entity, InputComponent, PositionComponent, TextComponent, VisualComponent, ECS};
 use glam::Vec2;
 use winit::event_loop::EventLoopProxy;
 
@@ -19,36 +17,32 @@ impl Factory {
 
         ecs.add_component(
             button,
-            Component::Position(PositionComponent {
+            PositionComponent {
                 xyz: position.extend(0.0),
                 scale: size.extend(1.0),
-            }),
+            },
         );
 
         match content {
             Content::Image { name, layer } => {
                 ecs.add_component(
                     button,
-                    Component::Visual(VisualComponent::new(
-                        vec![ecs.get_texture_index(name)],
-                        layer,
-                        0,
-                    )),
+                    VisualComponent::new(vec![ecs.get_texture_index(name)], layer, 0.0),
                 );
             }
             Content::Text(content) => {
-                ecs.add_component(button, Component::Text(TextComponent::create(content)));
+                ecs.add_component(button, TextComponent::create(content));
             }
         }
 
         ecs.add_component(
             button,
-            Component::Input(InputComponent {
+            InputComponent {
                 is_active: is_focused,
                 activate: callback,
                 next: None,
                 previous: None,
-            }),
+            },
         );
 
         button
@@ -1,7 +1,5 @@
-use ecs::{
-    Component, Entity, Layer, PositionComponent, TextComponent, TextContent, VisualComponent, ECS,
-};
-use glam::Vec2;
+use ecs::{Entity, Layer, PositionComponent, TextComponent, TextContent, VisualComponent, ECS};
+use glam::{Vec2, Vec3};
 
 use super::Factory;
 
@@ -13,26 +11,41 @@ impl Factory {
         icon: &str,
         text: TextContent,
     ) -> Entity {
-        let icon_with_text: u32 = ecs.create_entity();
+        let icon_with_text = ecs.create_entity();
 
         ecs.add_component(
             icon_with_text,
-            Component::Position(PositionComponent {
+            PositionComponent {
                 xyz: position.extend(0.0),
                 scale: size.extend(1.0),
-            }),
+            },
         );
 
+        // children are placed relative to the parent, the icon takes the left part
+        let icon_entity = ecs.create_entity();
         ecs.add_component(
-            icon_with_text,
-            Component::Visual(VisualComponent::new(
-                vec![ecs.get_texture_index(icon)],
-                Layer::Interface,
-                0,
-            )),
+            icon_entity,
+            PositionComponent {
+                xyz: Vec3::new(-0.3, 0.0, 0.0),
+                scale: Vec3::new(0.4, 1.0, 1.0),
+            },
+        );
+        ecs.add_component(
+            icon_entity,
+            VisualComponent::new(vec![ecs.get_texture_index(icon)], Layer::Interface, 0.0),
         );
+        ecs.set_parent(icon_entity, icon_with_text);
 
-        ecs.add_component(icon_with_text, Component::Text(TextComponent::create(text)));
+        let text_entity = ecs.create_entity();
+        ecs.add_component(
+            text_entity,
+            PositionComponent {
+                xyz: Vec3::new(0.2, 0.0, 0.0),
+                scale: Vec3::new(0.6, 1.0, 1.0),
+            },
+        );
+        ecs.add_component(text_entity, TextComponent::create(text));
+        ecs.set_parent(text_entity, icon_with_text);
 
         icon_with_text
     }
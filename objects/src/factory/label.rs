@@ -1,6 +1,6 @@
 use super::{super::Content, Factory};
 
-use ecs::{Component, Entity, PositionComponent, TextComponent, VisualComponent, ECS};
+use ecs::{Entity, PositionComponent, TextComponent, VisualComponent, ECS};
 use glam::{Vec2, Vec3};
 
 pub trait VecTExtend {
@@ -29,24 +29,20 @@ impl Factory {
         let label = ecs.create_entity();
         ecs.add_component(
             label,
-            Component::Position(PositionComponent {
+            PositionComponent {
                 xyz: position.extend(0.0),
                 scale: size.extend(1.0),
-            }),
+            },
         );
 
         match content {
             Content::Text(content) => {
-                ecs.add_component(label, Component::Text(TextComponent::create(content)));
+                ecs.add_component(label, TextComponent::create(content));
             }
             Content::Image { name, layer } => {
                 ecs.add_component(
                     label,
-                    Component::Visual(VisualComponent::new(
-                        vec![ecs.get_texture_index(name)],
-                        layer,
-                        0,
-                    )),
+                    VisualComponent::new(vec![ecs.get_texture_index(name)], layer, 0.0),
                 );
             }
         }
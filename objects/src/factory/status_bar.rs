@@ -1,4 +1,4 @@
-use ecs::{Component, Entity, Layer, PositionComponent, VisualComponent, ECS};
+use ecs::{Entity, Layer, PositionComponent, VisualComponent, ECS};
 use glam::Vec2;
 
 use super::Factory;
@@ -9,19 +9,19 @@ impl Factory {
 
         ecs.add_component(
             status_bar,
-            Component::Position(PositionComponent {
+            PositionComponent {
                 xyz: position.extend(0.0),
                 scale: size.extend(1.0),
-            }),
+            },
         );
 
         ecs.add_component(
             status_bar,
-            Component::Visual(VisualComponent::new(
+            VisualComponent::new(
                 vec![ecs.get_texture_index("empty_bar")],
                 Layer::Interface,
-                0,
-            )),
+                0.0,
+            ),
         );
 
         status_bar
@@ -1,8 +1,10 @@
 mod factory;
+mod prefab;
 
 use ecs::{Layer, TextContent};
 
 pub use factory::Factory;
+pub use prefab::{Actions, Prefab, PrefabError, PrefabPool};
 
 pub enum Content<'a> {
     Text(TextContent),
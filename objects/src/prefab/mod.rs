@@ -0,0 +1,411 @@
+mod pool;
+
+use ecs::{
+    Entity, InputComponent, Layer, PhysicsComponent, PositionComponent, TextComponent, TextContent,
+    VisualComponent, ECS,
+};
+use glam::Vec3;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+};
+use winit::event_loop::EventLoopProxy;
+
+pub use pool::PrefabPool;
+
+// buttons refer to their callback by name
+pub type Actions<E> = HashMap<&'static str, fn(&EventLoopProxy<E>) -> ()>;
+
+// entities described in a json file, see res/scenes for examples
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Prefab {
+    entities: Vec<PrefabEntity>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PrefabEntity {
+    name: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    position: Option<PrefabPosition>,
+    size: Option<PrefabSize>,
+    image: Option<PrefabImage>,
+    text: Option<TextContent>,
+    velocity: Option<[f32; 3]>,
+    button: Option<PrefabButton>,
+    // positions of children are relative to this entity
+    #[serde(default)]
+    children: Vec<PrefabEntity>,
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PrefabPosition {
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    z: f32,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PrefabSize {
+    width: f32,
+    height: f32,
+}
+
+impl Default for PrefabSize {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            height: 1.0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PrefabImage {
+    textures: Vec<String>,
+    layer: Layer,
+    // seconds per texture
+    #[serde(default)]
+    frame_duration: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PrefabButton {
+    action: String,
+    #[serde(default)]
+    focused: bool,
+    // names of other entities of the same prefab
+    next: Option<String>,
+    previous: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum PrefabError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    UnknownTexture(String),
+    // the name of the entity, if it has one
+    NoTextures(Option<String>),
+    UnknownAction(String),
+    UnknownLink(String),
+    DuplicateName(String),
+}
+
+impl Display for PrefabError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            PrefabError::Io(error) => write!(formatter, "prefab io failed: {error}"),
+            PrefabError::Json(error) => write!(formatter, "prefab is not valid: {error}"),
+            PrefabError::UnknownTexture(name) => write!(formatter, "unknown texture: {name}"),
+            PrefabError::NoTextures(Some(name)) => write!(formatter, "{name} has no textures"),
+            PrefabError::NoTextures(None) => write!(formatter, "image without textures"),
+            PrefabError::UnknownAction(name) => write!(formatter, "unknown action: {name}"),
+            PrefabError::UnknownLink(name) => write!(formatter, "unknown link target: {name}"),
+            PrefabError::DuplicateName(name) => write!(formatter, "duplicate name: {name}"),
+        }
+    }
+}
+
+impl std::error::Error for PrefabError {}
+
+impl Prefab {
+    pub fn from_json(json: &str) -> Result<Self, PrefabError> {
+        serde_json::from_str(json).map_err(PrefabError::Json)
+    }
+
+    pub fn read_from<P>(path: P) -> Result<Self, PrefabError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_json(&fs::read_to_string(path).map_err(PrefabError::Io)?)
+    }
+
+    // creates all entities and returns the roots, nothing is created if the prefab is invalid
+    pub fn spawn<E>(
+        &self,
+        ecs: &mut ECS<E>,
+        actions: &Actions<E>,
+    ) -> Result<Vec<Entity>, PrefabError>
+    where
+        E: 'static,
+    {
+        let mut names = Vec::new();
+        for entity in &self.entities {
+            entity.validate(ecs, actions, &mut names)?;
+        }
+        for entity in &self.entities {
+            entity.validate_links(&names)?;
+        }
+
+        let mut spawned = Spawned {
+            named: HashMap::with_capacity(names.len()),
+            buttons: Vec::new(),
+        };
+        let roots = self
+            .entities
+            .iter()
+            .map(|entity| entity.spawn(ecs, actions, &mut spawned))
+            .collect();
+
+        // links can point to entities that are spawned later, so they are set last
+        for (entity, button) in spawned.buttons {
+            if let Some(next) = &button.next {
+                ecs.set_next_of(entity, spawned.named[next.as_str()]);
+            }
+            if let Some(previous) = &button.previous {
+                ecs.set_previous_of(entity, spawned.named[previous.as_str()]);
+            }
+        }
+
+        Ok(roots)
+    }
+}
+
+struct Spawned<'prefab> {
+    named: HashMap<&'prefab str, Entity>,
+    buttons: Vec<(Entity, &'prefab PrefabButton)>,
+}
+
+impl PrefabEntity {
+    fn validate<'prefab, E>(
+        &'prefab self,
+        ecs: &ECS<E>,
+        actions: &Actions<E>,
+        names: &mut Vec<&'prefab str>,
+    ) -> Result<(), PrefabError>
+    where
+        E: 'static,
+    {
+        if let Some(name) = &self.name {
+            if names.contains(&name.as_str()) {
+                return Err(PrefabError::DuplicateName(name.clone()));
+            }
+            names.push(name);
+        }
+
+        if let Some(image) = &self.image {
+            // a visual needs at least one texture to draw
+            if image.textures.is_empty() {
+                return Err(PrefabError::NoTextures(self.name.clone()));
+            }
+
+            for texture in &image.textures {
+                if ecs.find_texture_index(texture).is_none() {
+                    return Err(PrefabError::UnknownTexture(texture.clone()));
+                }
+            }
+        }
+
+        if let Some(button) = &self.button {
+            if !actions.contains_key(button.action.as_str()) {
+                return Err(PrefabError::UnknownAction(button.action.clone()));
+            }
+        }
+
+        for child in &self.children {
+            child.validate(ecs, actions, names)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_links(&self, names: &[&str]) -> Result<(), PrefabError> {
+        if let Some(button) = &self.button {
+            for link in [&button.next, &button.previous].into_iter().flatten() {
+                if !names.contains(&link.as_str()) {
+                    return Err(PrefabError::UnknownLink(link.clone()));
+                }
+            }
+        }
+
+        for child in &self.children {
+            child.validate_links(names)?;
+        }
+
+        Ok(())
+    }
+
+    fn spawn<'prefab, E>(
+        &'prefab self,
+        ecs: &mut ECS<E>,
+        actions: &Actions<E>,
+        spawned: &mut Spawned<'prefab>,
+    ) -> Entity
+    where
+        E: 'static,
+    {
+        let entity = ecs.create_entity();
+
+        if self.position.is_some() || self.size.is_some() {
+            let position = self.position.unwrap_or_default();
+            let size = self.size.unwrap_or_default();
+
+            ecs.add_component(
+                entity,
+                PositionComponent {
+                    xyz: Vec3::new(position.x, position.y, position.z),
+                    scale: Vec3::new(size.width, size.height, 1.0),
+                },
+            );
+        }
+
+        if let Some(image) = &self.image {
+            let textures = image
+                .textures
+                .iter()
+                .map(|texture| ecs.get_texture_index(texture))
+                .collect();
+
+            ecs.add_component(
+                entity,
+                VisualComponent::new(textures, image.layer, image.frame_duration),
+            );
+        }
+
+        if let Some(text) = &self.text {
+            ecs.add_component(entity, TextComponent::create(text.clone()));
+        }
+
+        if let Some(velocity) = self.velocity {
+            ecs.add_component(
+                entity,
+                PhysicsComponent {
+                    velocity: Vec3::from_array(velocity),
+                },
+            );
+        }
+
+        if let Some(button) = &self.button {
+            ecs.add_component(
+                entity,
+                InputComponent {
+                    is_active: button.focused,
+                    activate: actions[button.action.as_str()],
+                    next: None,
+                    previous: None,
+                },
+            );
+            spawned.buttons.push((entity, button));
+        }
+
+        if let Some(name) = &self.name {
+            ecs.set_name(entity, name);
+            spawned.named.insert(name, entity);
+        }
+
+        for tag in &self.tags {
+            ecs.add_tag(entity, tag);
+        }
+
+        for child in &self.children {
+            let child = child.spawn(ecs, actions, spawned);
+            ecs.set_parent(child, entity);
+        }
+
+        entity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Actions, Prefab, PrefabError};
+
+    use ecs::ECS;
+    use std::{
+        env, fs, process,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use winit::event_loop::EventLoopProxy;
+
+    static NEXT_TABLE: AtomicUsize = AtomicUsize::new(0);
+
+    // "grass" falls back to the 404 image, the name is all validation looks at
+    pub(super) fn ecs() -> ECS<()> {
+        let path = env::temp_dir().join(format!(
+            "arcanaith_prefab_table_{}_{}.json",
+            process::id(),
+            NEXT_TABLE.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(
+            &path,
+            r#"{"textures": [{"name": "grass", "path": "missing.png"}], "sprites": []}"#,
+        )
+        .expect("Failed to write texture table!");
+
+        let ecs = ECS::create(&path.to_string_lossy(), "fonts").expect("Failed to create ecs!");
+        let _ = fs::remove_file(path);
+
+        ecs
+    }
+
+    pub(super) fn actions() -> Actions<()> {
+        let mut actions: Actions<()> = Actions::new();
+        actions.insert("start", |_: &EventLoopProxy<()>| ());
+
+        actions
+    }
+
+    fn spawn(json: &str) -> Result<usize, PrefabError> {
+        let mut ecs = ecs();
+        let result = Prefab::from_json(json).and_then(|prefab| prefab.spawn(&mut ecs, &actions()));
+
+        // nothing may be spawned if the prefab is invalid
+        if result.is_err() {
+            assert_eq!(ecs.entity_count(), 0);
+        }
+
+        result.map(|roots| roots.len())
+    }
+
+    #[test]
+    fn valid_prefabs_are_spawned() {
+        let json = r#"{"entities": [
+            {
+                "name": "field",
+                "image": { "textures": ["grass"], "layer": "Background" },
+                "children": [{ "name": "flower" }]
+            },
+            { "name": "start", "button": { "action": "start", "next": "start", "previous": "start" } }
+        ]}"#;
+
+        assert_eq!(spawn(json).expect("Failed to spawn valid prefab!"), 2);
+    }
+
+    #[test]
+    fn invalid_prefabs_are_rejected() {
+        assert!(matches!(
+            spawn(r#"{"entities": [{ "speed": 1.0 }]}"#),
+            Err(PrefabError::Json(_))
+        ));
+        assert!(matches!(
+            spawn(r#"{"entities": [{ "image": { "textures": ["sand"], "layer": "Background" } }]}"#),
+            Err(PrefabError::UnknownTexture(name)) if name == "sand"
+        ));
+        assert!(matches!(
+            spawn(r#"{"entities": [{ "name": "field", "image": { "textures": [], "layer": "Background" } }]}"#),
+            Err(PrefabError::NoTextures(Some(name))) if name == "field"
+        ));
+        assert!(matches!(
+            spawn(r#"{"entities": [{ "button": { "action": "quit" } }]}"#),
+            Err(PrefabError::UnknownAction(name)) if name == "quit"
+        ));
+        assert!(matches!(
+            spawn(r#"{"entities": [{ "button": { "action": "start", "next": "options" } }]}"#),
+            Err(PrefabError::UnknownLink(name)) if name == "options"
+        ));
+        assert!(matches!(
+            spawn(r#"{"entities": [{ "name": "twin" }, { "children": [{ "name": "twin" }] }]}"#),
+            Err(PrefabError::DuplicateName(name)) if name == "twin"
+        ));
+    }
+}
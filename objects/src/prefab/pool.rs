@@ -0,0 +1,118 @@
+use super::{Actions, Prefab, PrefabError};
+
+use ecs::{Entity, EntityPool, ECS};
+
+// instances of one prefab for objects that come and go often, e.g. projectiles
+pub struct PrefabPool {
+    prefab: Prefab,
+    pool: EntityPool,
+}
+
+impl PrefabPool {
+    #[must_use]
+    pub fn new(prefab: Prefab) -> Self {
+        Self {
+            prefab,
+            pool: EntityPool::default(),
+        }
+    }
+
+    // an instance is the root of the prefab, prefabs with several roots are grouped under a parent
+    pub fn acquire<E>(
+        &mut self,
+        ecs: &mut ECS<E>,
+        actions: &Actions<E>,
+    ) -> Result<Entity, PrefabError>
+    where
+        E: 'static,
+    {
+        if let Some(instance) = self.pool.reuse(ecs) {
+            return Ok(instance);
+        }
+
+        let roots = self.prefab.spawn(ecs, actions)?;
+        if let [root] = roots[..] {
+            return Ok(root);
+        }
+
+        let group = ecs.create_entity();
+        for root in roots {
+            ecs.set_parent(root, group);
+        }
+
+        Ok(group)
+    }
+
+    pub fn release<E>(&mut self, ecs: &mut ECS<E>, instance: Entity)
+    where
+        E: 'static,
+    {
+        self.pool.release(ecs, instance);
+    }
+
+    pub fn clear<E>(&mut self, ecs: &mut ECS<E>)
+    where
+        E: 'static,
+    {
+        self.pool.clear(ecs);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pool.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::tests::{actions, ecs},
+        Prefab, PrefabPool,
+    };
+
+    #[test]
+    fn prefabs_with_several_roots_are_grouped() {
+        let mut ecs = ecs();
+        let prefab =
+            Prefab::from_json(r#"{"entities": [{ "name": "left" }, { "name": "right" }]}"#)
+                .expect("Failed to parse prefab!");
+        let mut pool = PrefabPool::new(prefab);
+
+        let group = pool
+            .acquire(&mut ecs, &actions())
+            .expect("Failed to spawn prefab!");
+        assert_eq!(ecs.get_children(group).len(), 2);
+        assert_eq!(ecs.entity_count(), 3);
+
+        pool.release(&mut ecs, group);
+        assert_eq!(pool.len(), 1);
+        assert!(ecs
+            .find_by_name("left")
+            .is_some_and(|left| !ecs.is_enabled(left)));
+
+        let reused = pool
+            .acquire(&mut ecs, &actions())
+            .expect("Failed to reuse prefab!");
+        assert_eq!(reused, group);
+        assert_eq!(ecs.entity_count(), 3);
+    }
+
+    #[test]
+    fn single_roots_are_not_grouped() {
+        let mut ecs = ecs();
+        let prefab = Prefab::from_json(r#"{"entities": [{ "name": "arrow", "children": [{}] }]}"#)
+            .expect("Failed to parse prefab!");
+        let mut pool = PrefabPool::new(prefab);
+
+        let arrow = pool
+            .acquire(&mut ecs, &actions())
+            .expect("Failed to spawn prefab!");
+        assert_eq!(ecs.find_by_name("arrow"), Some(arrow));
+        assert_eq!(ecs.entity_count(), 2);
+    }
+}
@@ -16,9 +16,11 @@ pub trait RenderAPI {
         frames_in_flight: usize,
         vertex_path: &str,
         frag_path: &str,
+        validation: bool,
     ) -> Self;
     fn draw(&mut self, textures: &[ImageView], positions: &[MVP]);
     fn create_image_data(&self, image: image::ImageBuffer<image::Rgba<u8>, Vec<u8>>) -> ImageData;
+    fn destroy_image_data(&mut self, image_data: ImageData);
     fn get_extent(&self) -> WindowSize;
     fn wait_idle(&self);
     #[allow(clippy::missing_safety_doc)]
@@ -54,6 +54,8 @@ pub struct VulkanAPI {
     in_flight: Vec<Fence>,
     current_frame: usize,
     frames_in_flight: usize,
+    // images waiting for the frames that may still use them, with the frames left to wait
+    retired_images: Vec<(usize, ImageData)>,
 }
 
 impl VulkanAPI {
@@ -131,9 +133,10 @@ impl RenderAPI for VulkanAPI {
         frames_in_flight: usize,
         vertex_path: &str,
         frag_path: &str,
+        validation: bool,
     ) -> Self {
         let entry = Entry::linked();
-        let vk_instance = Vulkan::create_vulkan_instance(&entry, window, title);
+        let vk_instance = Vulkan::create_vulkan_instance(&entry, window, title, validation);
         let (surface, surface_loader) = Vulkan::create_surface(window, &entry, &vk_instance);
         let (physical_device, queue_family_index) =
             Vulkan::find_physical_device(&vk_instance, surface, &surface_loader);
@@ -264,6 +267,7 @@ impl RenderAPI for VulkanAPI {
             in_flight,
             current_frame: 0,
             frames_in_flight,
+            retired_images: Vec::new(),
         }
     }
 
@@ -274,6 +278,17 @@ impl RenderAPI for VulkanAPI {
         }
         .expect("Failed to wait for fences!");
 
+        // every waited fence means one more frame that could have used a retired image is done
+        self.retired_images.retain_mut(|(frames_left, image_data)| {
+            *frames_left -= 1;
+            if *frames_left > 0 {
+                return true;
+            }
+
+            image_data.destroy();
+            false
+        });
+
         let Ok((image_index, _suboptimal /* ignore suboptimal for performance */)) = (unsafe {
             self.swapchain_loader.acquire_next_image(
                 self.swapchain,
@@ -405,6 +420,11 @@ impl RenderAPI for VulkanAPI {
         )
     }
 
+    fn destroy_image_data(&mut self, image_data: ImageData) {
+        self.retired_images
+            .push((self.frames_in_flight, image_data));
+    }
+
     fn get_extent(&self) -> WindowSize {
         WindowSize {
             width: self.extent.width,
@@ -417,6 +437,10 @@ impl RenderAPI for VulkanAPI {
     }
 
     unsafe fn destroy(&self) {
+        for (_, image_data) in &self.retired_images {
+            image_data.destroy();
+        }
+
         self.device.destroy_buffer(self.index_buffer, None);
         self.device.free_memory(self.index_buffer_memory, None);
 
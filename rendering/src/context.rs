@@ -17,6 +17,7 @@ impl<API: RenderAPI> RenderContext<API> {
         in_flight: usize,
         vertex_path: &str,
         frag_path: &str,
+        validation: bool,
     ) -> Self {
         Self {
             api: API::create(
@@ -26,6 +27,7 @@ impl<API: RenderAPI> RenderContext<API> {
                 in_flight,
                 vertex_path,
                 frag_path,
+                validation,
             ),
         }
     }
@@ -51,4 +53,8 @@ impl<API: RenderAPI> Renderer for RenderContext<API> {
     fn create_image_data(&self, image: ImageBuffer<Rgba<u8>, Vec<u8>>) -> ImageData {
         self.api.create_image_data(image)
     }
+
+    fn destroy_image_data(&mut self, image_data: ImageData) {
+        self.api.destroy_image_data(image_data);
+    }
 }
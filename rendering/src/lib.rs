@@ -13,4 +13,6 @@ use image::{ImageBuffer, Rgba};
 pub trait Renderer {
     fn draw(&mut self, textures: &[ImageView], positions: &[MVP]);
     fn create_image_data(&self, image: ImageBuffer<Rgba<u8>, Vec<u8>>) -> ImageData;
+    // destroyed once no frame in flight can use it anymore
+    fn destroy_image_data(&mut self, image_data: ImageData);
 }
@@ -2,6 +2,7 @@ use ash::vk::{
     Bool32, DebugUtilsMessageSeverityFlagsEXT, DebugUtilsMessageTypeFlagsEXT,
     DebugUtilsMessengerCallbackDataEXT, FALSE,
 };
+use log::Level;
 use std::ffi::{c_void, CStr};
 
 pub unsafe extern "system" fn debug_callback(
@@ -11,10 +12,11 @@ pub unsafe extern "system" fn debug_callback(
     _p_user_data: *mut c_void,
 ) -> Bool32 {
     let message = CStr::from_ptr((*p_callback_data).p_message).to_string_lossy();
-    let severity = match message_severity {
-        DebugUtilsMessageSeverityFlagsEXT::WARNING => "WARNING",
-        DebugUtilsMessageSeverityFlagsEXT::ERROR => "ERROR",
-        _ => "UNKNOWN",
+    let level = match message_severity {
+        DebugUtilsMessageSeverityFlagsEXT::ERROR => Level::Error,
+        DebugUtilsMessageSeverityFlagsEXT::WARNING => Level::Warn,
+        DebugUtilsMessageSeverityFlagsEXT::INFO => Level::Info,
+        _ => Level::Trace,
     };
     let message_type = match message_type {
         DebugUtilsMessageTypeFlagsEXT::VALIDATION => "VALIDATION",
@@ -22,6 +24,7 @@ pub unsafe extern "system" fn debug_callback(
         _ => "UNKNOWN",
     };
 
-    println!("[VULKAN]: {severity} - {message_type}: {message}");
+    // filtered like any other target, e.g. "vulkan": "error" in the config
+    log::log!(target: "vulkan", level, "{message_type}: {message}");
     FALSE
 }
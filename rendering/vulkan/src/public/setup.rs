@@ -38,6 +38,7 @@ impl Vulkan {
         entry: &Entry,
         window: &winit::window::Window,
         title: &str,
+        validation: bool,
     ) -> Instance {
         let display_handle = window
             .display_handle()
@@ -50,7 +51,7 @@ impl Vulkan {
             .application_version(1)
             .api_version(API_VERSION_1_3);
 
-        if cfg!(debug_assertions) {
+        if validation {
             let mut extension_names = enumerate_required_extensions(display_handle)
                 .expect("Failed to get required extensions!")
                 .to_vec();
@@ -61,7 +62,9 @@ impl Vulkan {
 
             let mut debug_utils_info = DebugUtilsMessengerCreateInfoEXT::default()
                 .message_severity(
-                    DebugUtilsMessageSeverityFlagsEXT::WARNING
+                    DebugUtilsMessageSeverityFlagsEXT::VERBOSE
+                        | DebugUtilsMessageSeverityFlagsEXT::INFO
+                        | DebugUtilsMessageSeverityFlagsEXT::WARNING
                         | DebugUtilsMessageSeverityFlagsEXT::ERROR,
                 )
                 .message_type(
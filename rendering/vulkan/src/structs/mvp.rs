@@ -1,9 +1,27 @@
 use glam::Mat4;
 
+// per instance data, the layout has to match the MVP struct in shaders/shader.vert
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct MVP {
     pub model: Mat4,
     pub view: Mat4,
     pub projection: Mat4,
+    // multiplied with the alpha of the texture
+    pub opacity: f32,
+    // std430 rounds the struct size up to 16 bytes
+    padding: [f32; 3],
+}
+
+impl MVP {
+    #[must_use]
+    pub fn new(model: Mat4, view: Mat4, projection: Mat4, opacity: f32) -> Self {
+        Self {
+            model,
+            view,
+            projection,
+            opacity,
+            padding: [0.0; 3],
+        }
+    }
 }
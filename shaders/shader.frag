@@ -5,6 +5,7 @@ layout(set = 0, binding = 1) uniform sampler2D objectTextures[];
 
 layout(location = 0) in vec2 fragTextureCoordinates;
 layout(location = 1) flat in int instanceIndex;
+layout(location = 2) flat in float fragOpacity;
 
 layout(location = 0) out vec4 outColor;
 
@@ -16,5 +17,5 @@ void main() {
     discard;
   }
 
-  outColor = textureColor;
+  outColor = vec4(textureColor.rgb, textureColor.a * fragOpacity);
 }
@@ -4,6 +4,7 @@ struct MVP {
   mat4 model;
   mat4 view;
   mat4 projection;
+  float opacity;
 };
 
 layout(set = 0, binding = 0) readonly buffer MVPs { MVP mvpMatrices[]; };
@@ -13,6 +14,7 @@ layout(location = 1) in vec2 inTextureCoordinates;
 
 layout(location = 0) out vec2 fragTextureCoordinates;
 layout(location = 1) flat out int instanceIndex;
+layout(location = 2) flat out float fragOpacity;
 
 void main() {
   gl_Position = mvpMatrices[gl_InstanceIndex].projection *
@@ -22,4 +24,5 @@ void main() {
 
   fragTextureCoordinates = inTextureCoordinates;
   instanceIndex = gl_InstanceIndex;
+  fragOpacity = mvpMatrices[gl_InstanceIndex].opacity;
 }